@@ -1,10 +1,15 @@
-use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use crate::id::IDError;
+use crate::namespace::{parse_namespace_uuid, NamespaceRegistry};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
 
 /// A lightweight, powerful CLI tool for generating and inspecting unique identifiers.
 ///
-/// Supports UUID (v1-v5), NanoID, CUID (v1/v2), ULID, and MongoDB ObjectID.
-#[derive(Parser, Debug)]
+/// Supports UUID (v1-v8), NanoID, CUID (v1/v2), ULID, MongoDB ObjectID, Snowflake, KSUID, TypeID, and xid.
+#[derive(Parser, Debug, Clone)]
 #[command(name = "idgen")]
 #[command(author = "Mohamed Aamir Maniar <aamir.maniar@maniartech.com>")]
 #[command(version)]
@@ -13,12 +18,38 @@ use clap_complete::Shell;
     idgen                                       Generate a random UUID v4 (default)
     idgen -t uuid1                              Generate a time-based UUID v1
     idgen -t uuid3 --namespace DNS --name example.com
+    idgen -t uuid7                              Generate a time-ordered UUID v7
+    idgen -t uuid1 --node machine               Generate a UUID v1 with a stable per-machine node ID
+    idgen -t uuid5 --namespace DNS --names-from hosts.txt
+    idgen -t uuid5 --namespace billing --name invoice-42 --define-namespace billing=<uuid>
+    idgen -t uuid5 --namespace DNS:acme.com/orders --name 42 --verbose
+    idgen namespaces                            List built-in and custom namespaces
+    idgen -t uuid8 --custom-a 0x2a              Generate a UUID v8 tagged with 0x2a
+    idgen convert f47ac10b-58cc-11e4-8b58-0800200c9a66
     idgen -t nanoid -l 10                       Generate a NanoID of length 10
+    idgen -t nanoid -a numbers -l 6             Generate a 6-digit numeric NanoID
     idgen -t ulid                               Generate a ULID
+    idgen -t ulid --at 2024-01-31T12:00:00Z     Generate a ULID stamped with a past time
+    idgen -t ulid --monotonic -c 1000          Generate ULIDs that sort in generation order
+    idgen -t ulid -c 100 --from 2024-01-01T00:00:00Z --to 2024-12-31T23:59:59Z --spread random --sorted
+    idgen -t snowflake --preset discord --worker-id 3
+    idgen -t typeid --type-prefix user          Generate a TypeID like user_01h455vb4pex5vsknk084sn02q
     idgen -c 5                                  Generate 5 UUIDs
+    idgen -t ulid --stream --rate 100/s --duration 1m
+    idgen -t uuid7 -c 1000000 --split-every 100000 -o ids.txt
+    idgen --profile invoice                     Generate with the settings of [profiles.invoice]
+    idgen --profile invoice config show         Show the effective settings and their sources
+    idgen -t uuid7 -c 100000000 --threads 8 --stats > ids.txt
+    idgen --template 'ord-{ulid}-{nanoid:6}'    Generate a composite ID
+    idgen --template '{date:%Y%m%d}-{seq:04}' -c 3
+    idgen --seed 42 -c 5                        Generate the same 5 UUIDs on every run (not secure)
     idgen -p 'test-' -s '.log'                  Add prefix and suffix
     idgen --json                                Output as JSON
+    idgen -t ulid -c 3 --output-format csv --columns type,timestamp
     idgen inspect 550e8400-e29b-44d4-a716-446655440000
+    idgen inspect 1541815603606036480 --snowflake twitter
+    idgen encode 1 2 3                          Encode integers into a Sqids ID
+    idgen decode 86Rf07                         Decode a Sqids ID back into integers
     idgen completions bash                      Generate bash completions")]
 pub struct Cli {
     /// Type of ID to generate
@@ -31,12 +62,17 @@ pub struct Cli {
 
     /// Number of IDs to generate
     #[arg(short = 'c', long = "count", default_value = "1")]
-    pub count: u32,
+    pub count: u64,
 
     /// Length for NanoID (default: 21)
     #[arg(short = 'l', long = "length")]
     pub length: Option<usize>,
 
+    /// Alphabet for NanoID: a preset (numbers, lowercase, uppercase, alphanumeric,
+    /// nolookalikes, hex) or the characters to use
+    #[arg(short = 'a', long = "alphabet")]
+    pub alphabet: Option<String>,
+
     /// Prefix to add to generated IDs
     #[arg(short = 'p', long = "prefix", default_value = "")]
     pub prefix: String,
@@ -45,7 +81,13 @@ pub struct Cli {
     #[arg(short = 's', long = "suffix", default_value = "")]
     pub suffix: String,
 
-    /// Namespace UUID for v3/v5 (use DNS, URL, OID, X500, or a custom UUID)
+    /// Render each ID from a template such as 'ord-{ulid}-{nanoid:6}' or
+    /// '{date:%Y%m%d}-{seq:06}' (placeholders: any ID type, seq, date)
+    #[arg(long = "template", conflicts_with = "names_from")]
+    pub template: Option<String>,
+
+    /// Namespace UUID for v3/v5 (use DNS, URL, OID, X500, a custom namespace name, or a UUID).
+    /// A path like DNS:acme.com/orders derives a namespace from each name in turn
     #[arg(long = "namespace")]
     pub namespace: Option<String>,
 
@@ -53,25 +95,259 @@ pub struct Cli {
     #[arg(long = "name")]
     pub name: Option<String>,
 
-    /// Output as JSON
+    /// Read UUID v3/v5 names from a file, one per line ('-' for stdin), and print
+    /// name<TAB>uuid for each
+    #[arg(long = "names-from", value_name = "FILE", conflicts_with = "name")]
+    pub names_from: Option<String>,
+
+    /// Define a custom namespace for this run as NAME=UUID (repeatable). Custom
+    /// namespaces can also be stored under [namespaces] in the config file
+    #[arg(long = "define-namespace", value_name = "NAME=UUID", global = true)]
+    pub define_namespace: Vec<String>,
+
+    /// Print --names-from records as CSV (same as --output-format csv)
+    #[arg(long = "csv", requires = "names_from", conflicts_with = "json")]
+    pub csv: bool,
+
+    /// Type prefix for TypeID (lowercase a-z and '_', up to 63 chars)
+    #[arg(long = "type-prefix", default_value = "")]
+    pub type_prefix: String,
+
+    /// UUID v1/v6 node ID: random (multicast, the default), machine (derived from the
+    /// machine ID), or 12 hex digits like 00:1a:2b:3c:4d:5e
+    #[arg(long = "node")]
+    pub node: Option<String>,
+
+    /// UUID v1/v6 clock sequence (0-16383; random if omitted)
+    #[arg(long = "clock-seq")]
+    pub clock_seq: Option<u16>,
+
+    /// Hex payload for UUID v8 (32 hex digits; version and variant bits are overwritten)
+    #[arg(long = "payload", conflicts_with_all = ["custom_a", "custom_b", "custom_c"])]
+    pub payload: Option<String>,
+
+    /// UUID v8 custom_a field (48 bits, decimal or 0x hex; random if omitted)
+    #[arg(long = "custom-a", value_parser = parse_int)]
+    pub custom_a: Option<u64>,
+
+    /// UUID v8 custom_b field (12 bits, decimal or 0x hex; random if omitted)
+    #[arg(long = "custom-b", value_parser = parse_int)]
+    pub custom_b: Option<u64>,
+
+    /// UUID v8 custom_c field (62 bits, decimal or 0x hex; random if omitted)
+    #[arg(long = "custom-c", value_parser = parse_int)]
+    pub custom_c: Option<u64>,
+
+    /// Snowflake bit layout preset
+    #[arg(long = "preset", value_enum, default_value = "twitter")]
+    pub preset: SnowflakePreset,
+
+    /// Snowflake epoch in milliseconds since the Unix epoch (overrides the preset)
+    #[arg(long = "epoch")]
+    pub epoch: Option<u64>,
+
+    /// Snowflake worker ID
+    #[arg(long = "worker-id", default_value = "0")]
+    pub worker_id: u64,
+
+    /// Snowflake datacenter ID
+    #[arg(long = "datacenter-id", default_value = "0")]
+    pub datacenter_id: u64,
+
+    /// Snowflake worker ID bits (overrides the preset)
+    #[arg(long = "worker-bits")]
+    pub worker_bits: Option<u8>,
+
+    /// Snowflake datacenter ID bits (overrides the preset)
+    #[arg(long = "datacenter-bits")]
+    pub datacenter_bits: Option<u8>,
+
+    /// Snowflake sequence bits (overrides the preset)
+    #[arg(long = "sequence-bits")]
+    pub sequence_bits: Option<u8>,
+
+    /// Embed this time instead of now, as RFC 3339 or Unix seconds (time-based types only)
+    #[arg(long = "at", value_parser = parse_timestamp, conflicts_with = "from")]
+    pub at: Option<SystemTime>,
+
+    /// Start of the time range to spread --count IDs across (time-based types only)
+    #[arg(long = "from", value_parser = parse_timestamp, requires = "to")]
+    pub from: Option<SystemTime>,
+
+    /// End of the time range to spread --count IDs across
+    #[arg(long = "to", value_parser = parse_timestamp, requires = "from")]
+    pub to: Option<SystemTime>,
+
+    /// How to place timestamps between --from and --to
+    #[arg(long = "spread", value_enum, default_value = "even", requires = "from")]
+    pub spread: Spread,
+
+    /// Output IDs spread with --from/--to in chronological order
+    #[arg(long = "sorted", requires = "from")]
+    pub sorted: bool,
+
+    /// Make ULIDs strictly increasing, even within the same millisecond
+    #[arg(long = "monotonic")]
+    pub monotonic: bool,
+
+    /// File that keeps the last monotonic ULID, so separate runs keep increasing
+    #[arg(long = "state-file", requires = "monotonic")]
+    pub state_file: Option<PathBuf>,
+
+    /// Seed a deterministic PRNG so the same arguments always print the same IDs (not secure)
+    #[arg(long = "seed")]
+    pub seed: Option<u64>,
+
+    /// Generate with N worker threads; output keeps the same order as with one
+    #[arg(
+        long = "threads",
+        value_name = "N",
+        default_value = "1",
+        conflicts_with = "names_from"
+    )]
+    pub threads: usize,
+
+    /// Write the output to this file instead of stdout. The file appears once it is complete.
+    #[arg(short = 'o', long = "output", value_name = "PATH")]
+    pub output: Option<PathBuf>,
+
+    /// Add to the end of the --output file instead of replacing it
+    #[arg(long = "append", requires = "output", conflicts_with = "split_every")]
+    pub append: bool,
+
+    /// Start a new --output file every N IDs, named like ids-0001.txt, ids-0002.txt
+    #[arg(
+        long = "split-every",
+        value_name = "N",
+        requires = "output",
+        conflicts_with = "names_from"
+    )]
+    pub split_every: Option<u64>,
+
+    /// Keep generating IDs until stdin closes, a signal arrives or --duration passes
+    #[arg(
+        long = "stream",
+        conflicts_with_all = ["count", "from", "names_from", "threads"]
+    )]
+    pub stream: bool,
+
+    /// Stop --stream after this long, such as 500ms, 30s, 5m or 2h
+    #[arg(long = "duration", value_parser = parse_duration, requires = "stream")]
+    pub duration: Option<Duration>,
+
+    /// Emit at most this many IDs with --stream, such as 100/s, 30/m or 5/h
+    #[arg(long = "rate", value_name = "N/s", value_parser = parse_rate, requires = "stream")]
+    pub rate: Option<f64>,
+
+    /// Print the number of IDs, elapsed time and IDs per second to stderr
+    #[arg(long = "stats", conflicts_with = "names_from")]
+    pub stats: bool,
+
+    /// Output as JSON (same as --output-format json)
     #[arg(long = "json")]
     pub json: bool,
 
+    /// Output format for generated IDs
+    #[arg(long = "output-format", value_enum, conflicts_with_all = ["json", "csv"])]
+    pub output_format: Option<OutputFormat>,
+
+    /// Extra columns for json, ndjson, csv and tsv output (comma-separated)
+    #[arg(
+        long = "columns",
+        value_enum,
+        value_delimiter = ',',
+        conflicts_with = "names_from"
+    )]
+    pub columns: Vec<Column>,
+
+    /// Leave out the header row of csv and tsv output
+    #[arg(long = "no-header")]
+    pub no_header: bool,
+
+    /// Print extra details to stderr, such as intermediate namespaces of a namespace path
+    #[arg(short = 'v', long = "verbose")]
+    pub verbose: bool,
+
     /// Show banner
     #[arg(short = 'b', long = "banner")]
     pub banner: bool,
 
+    /// Apply the settings of [profiles.NAME] from the config files
+    #[arg(long = "profile", value_name = "NAME", global = true)]
+    pub profile: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
 pub enum Commands {
     /// Inspect an ID to determine its type and extract metadata
     Inspect {
         /// The ID string to inspect
         id: String,
 
+        /// Decode the ID as a Snowflake with this layout preset
+        #[arg(long = "snowflake", value_enum)]
+        snowflake: Option<SnowflakePreset>,
+
+        /// Output as JSON (same as --output-format json)
+        #[arg(long = "json")]
+        json: bool,
+
+        /// Output format: plain text, or one record in json, ndjson, csv or tsv
+        #[arg(long = "output-format", value_enum, conflicts_with = "json")]
+        output_format: Option<OutputFormat>,
+
+        /// Leave out the header row of csv and tsv output
+        #[arg(long = "no-header")]
+        no_header: bool,
+    },
+
+    /// Encode integers into a Sqids ID
+    Encode {
+        /// Integers to encode (all into one ID unless --each is given)
+        #[arg(required = true)]
+        numbers: Vec<u64>,
+
+        /// Encode each integer into its own ID
+        #[arg(long = "each")]
+        each: bool,
+
+        #[command(flatten)]
+        sqids: SqidsArgs,
+
+        /// Output as JSON
+        #[arg(long = "json")]
+        json: bool,
+    },
+
+    /// Decode Sqids IDs back into integers
+    Decode {
+        /// The Sqids IDs to decode
+        #[arg(required = true)]
+        ids: Vec<String>,
+
+        #[command(flatten)]
+        sqids: SqidsArgs,
+
+        /// Output as JSON
+        #[arg(long = "json")]
+        json: bool,
+    },
+
+    /// Convert a UUID v1 to its v6 form, or a UUID v6 back to v1
+    Convert {
+        /// The UUID v1 or v6 to convert
+        id: String,
+
+        /// Output format for the converted UUID
+        #[arg(short = 'f', long = "format", value_enum, default_value = "hyphenated")]
+        format: UuidFormat,
+    },
+
+    /// List the built-in and custom namespaces for UUID v3/v5
+    Namespaces {
         /// Output as JSON
         #[arg(long = "json")]
         json: bool,
@@ -84,11 +360,47 @@ pub enum Commands {
         shell: Shell,
     },
 
+    /// Inspect the settings from config files and profiles
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
     /// Generate man page
     #[command(name = "manpage", hide = true)]
     ManPage,
 }
 
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigAction {
+    /// Print the effective settings and where each value came from
+    Show {
+        /// Output as JSON
+        #[arg(long = "json")]
+        json: bool,
+    },
+}
+
+/// Sqids settings shared by `encode` and `decode`; both sides must use the same values
+#[derive(Args, Debug, Clone)]
+pub struct SqidsArgs {
+    /// Custom alphabet (at least 3 unique single-byte characters)
+    #[arg(long = "alphabet")]
+    pub alphabet: Option<String>,
+
+    /// Minimum length of the generated IDs
+    #[arg(long = "min-length", default_value = "0")]
+    pub min_length: u8,
+
+    /// Extra words to keep out of generated IDs (comma-separated)
+    #[arg(long = "blocklist", value_delimiter = ',')]
+    pub blocklist: Vec<String>,
+
+    /// Do not use the built-in blocklist
+    #[arg(long = "no-default-blocklist")]
+    pub no_default_blocklist: bool,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum IdType {
     /// UUID version 1 (time-based)
@@ -107,6 +419,18 @@ pub enum IdType {
     #[value(name = "uuid5", alias = "u5")]
     Uuid5,
 
+    /// UUID version 6 (time-based, reordered for sorting)
+    #[value(name = "uuid6", alias = "u6")]
+    Uuid6,
+
+    /// UUID version 7 (Unix timestamp-ordered, random)
+    #[value(name = "uuid7", alias = "u7")]
+    Uuid7,
+
+    /// UUID version 8 (custom payload via --payload or --custom-a/b/c)
+    #[value(name = "uuid8", alias = "u8")]
+    Uuid8,
+
     /// NanoID (URL-safe, configurable length)
     #[value(name = "nanoid", alias = "nano")]
     NanoId,
@@ -126,6 +450,82 @@ pub enum IdType {
     /// MongoDB ObjectID
     #[value(name = "objectid", alias = "oid")]
     ObjectId,
+
+    /// Snowflake ID (64-bit, time-ordered; see --preset)
+    #[value(name = "snowflake", alias = "sf")]
+    Snowflake,
+
+    /// KSUID (K-Sortable Unique IDentifier, 27-char base62)
+    #[value(name = "ksuid")]
+    Ksuid,
+
+    /// TypeID (type prefix + base32-encoded UUID v7; see --type-prefix)
+    #[value(name = "typeid", alias = "tid")]
+    TypeId,
+
+    /// xid (12-byte Mongo-like ID, 20-char base32hex)
+    #[value(name = "xid")]
+    Xid,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SnowflakePreset {
+    /// Twitter (41-bit timestamp, 5-bit datacenter, 5-bit worker, 12-bit sequence)
+    #[value(name = "twitter")]
+    Twitter,
+
+    /// Discord (42-bit timestamp, 5-bit worker, 5-bit process, 12-bit increment)
+    #[value(name = "discord")]
+    Discord,
+
+    /// Instagram (41-bit timestamp, 13-bit shard, 10-bit sequence)
+    #[value(name = "instagram")]
+    Instagram,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// One ID per line
+    #[value(name = "plain")]
+    Plain,
+
+    /// A JSON array of records
+    #[value(name = "json")]
+    Json,
+
+    /// One JSON record per line
+    #[value(name = "ndjson")]
+    Ndjson,
+
+    /// Comma-separated values with a header row
+    #[value(name = "csv")]
+    Csv,
+
+    /// Tab-separated values with a header row
+    #[value(name = "tsv")]
+    Tsv,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Column {
+    /// The ID type, as given to -t
+    #[value(name = "type")]
+    Type,
+
+    /// The embedded timestamp as RFC 3339 (empty for types without one)
+    #[value(name = "timestamp")]
+    Timestamp,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Spread {
+    /// Equal steps from --from to --to, both ends included
+    #[value(name = "even")]
+    Even,
+
+    /// Uniformly random times within the range
+    #[value(name = "random")]
+    Random,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
@@ -143,25 +543,142 @@ pub enum UuidFormat {
     Urn,
 }
 
-/// Well-known namespace UUIDs
-pub fn resolve_namespace(namespace: &str) -> Result<String, String> {
-    match namespace.to_uppercase().as_str() {
-        "DNS" => Ok("6ba7b810-9dad-11d1-80b4-00c04fd430c8".to_string()),
-        "URL" => Ok("6ba7b811-9dad-11d1-80b4-00c04fd430c8".to_string()),
-        "OID" => Ok("6ba7b812-9dad-11d1-80b4-00c04fd430c8".to_string()),
-        "X500" => Ok("6ba7b814-9dad-11d1-80b4-00c04fd430c8".to_string()),
-        _ => {
-            // Assume it's a custom UUID - validate format
-            if namespace.len() >= 32 && (namespace.len() == 32 || namespace.len() == 36) {
-                Ok(namespace.to_string())
-            } else {
-                Err(format!(
-                    "Invalid namespace '{}'. Use DNS, URL, OID, X500, or a valid UUID.",
+/// Resolves a namespace name (built-in or custom) or UUID string to a UUID
+pub fn resolve_namespace(namespace: &str, registry: &NamespaceRegistry) -> Result<Uuid, IDError> {
+    if let Some(uuid) = registry.get(namespace) {
+        return Ok(uuid);
+    }
+
+    parse_namespace_uuid(namespace).map_err(|err| {
+        IDError::InvalidNamespace(format!(
+            "{}\nUse DNS, URL, OID, X500, a custom namespace name, or a UUID.",
+            err
+        ))
+    })
+}
+
+/**
+ * Resolves a namespace that may be a path like `DNS:acme.com/orders`
+ *
+ * The part before the first `:` is resolved with `resolve_namespace`, then each
+ * `/`-separated name is hashed into the namespace before it with `derive`
+ * (`Uuid::new_v3` or `Uuid::new_v5`). A namespace without `:` is resolved as is.
+ * The `:` of a `urn:uuid:` base does not start the path.
+ *
+ * # Returns
+ *
+ * Every step as `(path, uuid)`, ending with the final namespace
+ */
+pub fn resolve_namespace_path(
+    namespace: &str,
+    registry: &NamespaceRegistry,
+    derive: fn(&Uuid, &[u8]) -> Uuid,
+) -> Result<Vec<(String, Uuid)>, IDError> {
+    let urn_len = if namespace
+        .get(..9)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("urn:uuid:"))
+    {
+        9
+    } else {
+        0
+    };
+    let (base, path) = match namespace[urn_len..].find(':') {
+        Some(index) => (
+            &namespace[..urn_len + index],
+            Some(&namespace[urn_len + index + 1..]),
+        ),
+        None => (namespace, None),
+    };
+
+    let mut current = resolve_namespace(base, registry)?;
+    let mut label = base.to_string();
+    let mut steps = vec![(label.clone(), current)];
+
+    if let Some(path) = path {
+        for (i, name) in path.split('/').enumerate() {
+            if name.is_empty() {
+                return Err(IDError::InvalidNamespace(format!(
+                    "Namespace path '{}' has an empty name; use BASE:name/name",
                     namespace
-                ))
+                )));
             }
+            current = derive(&current, name.as_bytes());
+            label.push(if i == 0 { ':' } else { '/' });
+            label.push_str(name);
+            steps.push((label.clone(), current));
         }
     }
+    Ok(steps)
+}
+
+/// Parses an unsigned integer given in decimal or with a 0x prefix
+fn parse_int(value: &str) -> Result<u64, String> {
+    let parsed = match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse::<u64>(),
+    };
+    parsed.map_err(|_| {
+        format!(
+            "'{}' is not a valid decimal or 0x-prefixed hex number",
+            value
+        )
+    })
+}
+
+/// Parses an RFC 3339 date-time or a number of seconds since the Unix epoch
+fn parse_timestamp(value: &str) -> Result<SystemTime, String> {
+    if let Ok(seconds) = value.parse::<u64>() {
+        return UNIX_EPOCH
+            .checked_add(Duration::from_secs(seconds))
+            .ok_or_else(|| format!("'{}' is too far in the future", value));
+    }
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(SystemTime::from)
+        .map_err(|_| {
+            format!(
+                "'{}' is not an RFC 3339 timestamp (e.g. 2024-01-31T12:00:00Z) or Unix seconds",
+                value
+            )
+        })
+}
+
+/// Parses a duration such as 500ms, 30s, 5m or 2h. A bare number is seconds.
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let (number, unit_seconds) = [("ms", 0.001), ("s", 1.0), ("m", 60.0), ("h", 3600.0)]
+        .iter()
+        .find_map(|(unit, seconds)| value.strip_suffix(unit).map(|number| (number, *seconds)))
+        .unwrap_or((value, 1.0));
+    number
+        .parse::<f64>()
+        .ok()
+        .and_then(|number| Duration::try_from_secs_f64(number * unit_seconds).ok())
+        .ok_or_else(|| format!("'{}' is not a duration such as 500ms, 30s, 5m or 2h", value))
+}
+
+/// Parses a rate such as 100/s, 30/m or 5/h into IDs per second. A bare number
+/// is per second.
+fn parse_rate(value: &str) -> Result<f64, String> {
+    let (number, per_seconds) = match value.split_once('/') {
+        Some((number, "s")) => (number, 1.0),
+        Some((number, "m")) => (number, 60.0),
+        Some((number, "h")) => (number, 3600.0),
+        Some(_) => (value, f64::NAN),
+        None => (value, 1.0),
+    };
+    number
+        .parse::<f64>()
+        .ok()
+        .map(|number| number / per_seconds)
+        .filter(|rate| rate.is_finite() && *rate > 0.0)
+        .ok_or_else(|| {
+            format!(
+                "'{}' is not a positive rate such as 100/s, 30/m or 5/h",
+                value
+            )
+        })
 }
 
 pub fn build_cli() -> clap::Command {
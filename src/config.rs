@@ -0,0 +1,442 @@
+use crate::cli::{build_cli, Cli};
+use crate::namespace::config_path;
+use clap::error::{ContextKind, ContextValue};
+use clap::parser::ValueSource;
+use clap::{Arg, ArgAction, ArgMatches, Command, FromArgMatches};
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+use std::env;
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use toml::{Table, Value};
+
+/// Name of the project config file, looked up from the current directory upwards
+pub const PROJECT_CONFIG_FILE: &str = ".idgen.toml";
+
+/// Environment variable that, when set to anything but an empty string, skips
+/// the user and project config files
+pub const NO_CONFIG_ENV: &str = "IDGEN_NO_CONFIG";
+
+/// Options that only make sense on the command line
+const COMMAND_LINE_ONLY: &[&str] = &["help", "version", "profile"];
+
+/// Options that read or write files. A project config may come from an
+/// untrusted checkout, so only the user config and the command line can set them.
+const NOT_IN_PROJECT_CONFIG: &[&str] = &[
+    "output",
+    "append",
+    "split-every",
+    "state-file",
+    "names-from",
+];
+
+/// Where the value of a setting came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingSource {
+    Default,
+    /// The top level of a config file
+    Config(PathBuf),
+    /// A `[profiles.NAME]` table of a config file
+    Profile(String, PathBuf),
+    CommandLine,
+}
+
+impl fmt::Display for SettingSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingSource::Default => write!(f, "default"),
+            SettingSource::Config(path) => write!(f, "config {}", path.display()),
+            SettingSource::Profile(name, path) => {
+                write!(f, "profile '{}' in {}", name, path.display())
+            }
+            SettingSource::CommandLine => write!(f, "command-line"),
+        }
+    }
+}
+
+impl Serialize for SettingSource {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// The effective value of one option
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Setting {
+    /// The long option name, which is also the config key
+    pub name: String,
+    pub value: String,
+    pub source: SettingSource,
+}
+
+/// A TOML config file: defaults at the top level, `[profiles.NAME]` tables and
+/// the `[namespaces]` table read by `NamespaceRegistry::load_config`
+#[derive(Debug, Clone)]
+pub struct ConfigFile {
+    pub path: PathBuf,
+    table: Table,
+}
+
+impl ConfigFile {
+    /// Reads a config file. A missing file is `None`.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(format!(
+                    "Cannot read config file '{}': {}",
+                    path.display(),
+                    err
+                ))
+            }
+        };
+        let table = contents
+            .parse::<Table>()
+            .map_err(|err| format!("Invalid config file '{}': {}", path.display(), err))?;
+        Ok(Some(ConfigFile {
+            path: path.to_path_buf(),
+            table,
+        }))
+    }
+
+    /// Whether this is a project config (`.idgen.toml`) rather than the user config
+    pub fn is_project(&self) -> bool {
+        self.path.file_name() == Some(PROJECT_CONFIG_FILE.as_ref())
+    }
+
+    /// The default settings at the top level of the file
+    pub fn defaults(&self) -> Table {
+        self.table
+            .iter()
+            .filter(|(key, _)| !matches!(key.as_str(), "namespaces" | "profiles"))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+
+    /// The settings of `[profiles.NAME]`, if the file defines that profile
+    pub fn profile(&self, name: &str) -> Result<Option<&Table>, String> {
+        match self.profiles()?.and_then(|profiles| profiles.get(name)) {
+            Some(Value::Table(profile)) => Ok(Some(profile)),
+            Some(_) => Err(format!(
+                "Profile '{}' in '{}' must be a table, like [profiles.{}]",
+                name,
+                self.path.display(),
+                name
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// The names of all profiles in the file
+    pub fn profile_names(&self) -> Vec<String> {
+        match self.profiles() {
+            Ok(Some(profiles)) => profiles.keys().cloned().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn profiles(&self) -> Result<Option<&Table>, String> {
+        match self.table.get("profiles") {
+            Some(Value::Table(profiles)) => Ok(Some(profiles)),
+            Some(_) => Err(format!(
+                "'profiles' in '{}' must be a table of [profiles.NAME] tables",
+                self.path.display()
+            )),
+            None => Ok(None),
+        }
+    }
+}
+
+/// `.idgen.toml` in the current directory, or in the nearest parent directory that has one
+pub fn project_config_path() -> Option<PathBuf> {
+    let dir = env::current_dir().ok()?;
+    dir.ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+/// The user config and then the project config paths, or none with `IDGEN_NO_CONFIG`
+pub fn config_paths() -> Vec<PathBuf> {
+    if env::var_os(NO_CONFIG_ENV).is_some_and(|value| !value.is_empty()) {
+        return Vec::new();
+    }
+    config_path()
+        .into_iter()
+        .chain(project_config_path())
+        .collect()
+}
+
+/// The user config and then the project config, those that exist
+pub fn config_files() -> Result<Vec<ConfigFile>, String> {
+    let mut files = Vec::new();
+    for path in config_paths() {
+        files.extend(ConfigFile::load(&path)?);
+    }
+    Ok(files)
+}
+
+/// The parsed command line with the config files applied
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub cli: Cli,
+    /// Every option that has a value, in the order of `--help`
+    pub settings: Vec<Setting>,
+}
+
+/**
+ * Parses the command line on top of the config files
+ *
+ * Every option can be set in a config file under its long name, such as
+ * `type = "nanoid"` or `length = 12`. From highest precedence to lowest, values
+ * come from the command line, the `--profile` in the project config and then
+ * the user config, the top level of the project config and then the user
+ * config, and the built-in defaults. A value is also skipped if it conflicts
+ * with one of higher precedence, so `--output-format csv` overrides a
+ * `json = true` default.
+ *
+ * Parse errors, `--help` and `--version` exit the process like `Cli::parse`.
+ *
+ * # Arguments
+ *
+ * * `args` - The command line, starting with the binary name
+ * * `files` - Config files, lowest precedence first
+ */
+pub fn load<I>(args: I, files: &[ConfigFile]) -> Result<Config, String>
+where
+    I: IntoIterator<Item = OsString>,
+{
+    let mut args: Vec<OsString> = args.into_iter().collect();
+    let command = build_cli();
+
+    // A lenient first pass finds what the command line sets. Its errors are
+    // reported by the full parse at the end.
+    let given = command
+        .clone()
+        .ignore_errors(true)
+        .try_get_matches_from(&args)
+        .ok();
+    let on_command_line = |arg: &&Arg| {
+        given.as_ref().is_some_and(|matches| {
+            matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine)
+        })
+    };
+    let profile = given.as_ref().and_then(|matches| {
+        matches
+            .try_get_one::<String>("profile")
+            .ok()
+            .flatten()
+            .cloned()
+    });
+
+    // Highest precedence first
+    let mut layers: Vec<(Table, SettingSource, &ConfigFile)> = Vec::new();
+    if let Some(name) = &profile {
+        for file in files.iter().rev() {
+            if let Some(table) = file.profile(name)? {
+                layers.push((
+                    table.clone(),
+                    SettingSource::Profile(name.clone(), file.path.clone()),
+                    file,
+                ));
+            }
+        }
+        if layers.is_empty() {
+            return Err(unknown_profile(name, files));
+        }
+    }
+    for file in files.iter().rev() {
+        layers.push((
+            file.defaults(),
+            SettingSource::Config(file.path.clone()),
+            file,
+        ));
+    }
+
+    // Options claimed so far, with the layer that set them (0 for the command line)
+    let mut claimed: Vec<(&Arg, usize)> = command
+        .get_arguments()
+        .filter(on_command_line)
+        .map(|arg| (arg, 0))
+        .collect();
+    let mut sources: HashMap<String, SettingSource> = HashMap::new();
+    let mut config_args = Vec::new();
+
+    for (layer, (table, source, file)) in layers.iter().enumerate() {
+        let layer = layer + 1;
+        for (key, value) in table {
+            let arg = find_arg(&command, key).ok_or_else(|| {
+                format!(
+                    "Unknown setting '{}' in {}; settings are named like long options, such as type or output-format",
+                    key, source
+                )
+            })?;
+            if file.is_project() && NOT_IN_PROJECT_CONFIG.contains(&key.as_str()) {
+                return Err(format!(
+                    "Setting '{}' in {} is not allowed; options that read or write files can only be set in the user config or on the command line",
+                    key, source
+                ));
+            }
+            let overridden = claimed.iter().any(|(other, other_layer)| {
+                *other_layer < layer
+                    && (other.get_id() == arg.get_id() || conflicts(&command, arg, other))
+            });
+            if overridden {
+                continue;
+            }
+
+            config_args.extend(
+                arg_values(arg, value)
+                    .map_err(|err| format!("Setting '{}' in {}: {}", key, source, err))?,
+            );
+            claimed.push((arg, layer));
+            sources.insert(arg.get_id().to_string(), source.clone());
+        }
+    }
+
+    // Config values go before the command line's own arguments, and before
+    // any subcommand
+    let at = args.len().min(1);
+    args.splice(at..at, config_args);
+    let matches = command
+        .clone()
+        .try_get_matches_from(&args)
+        .unwrap_or_else(|err| exit_with_source(err, &command, &sources));
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+
+    Ok(Config {
+        cli,
+        settings: settings(&command, &matches, &sources),
+    })
+}
+
+/// A top-level option by its long name, unless it is command-line only
+fn find_arg<'a>(command: &'a Command, key: &str) -> Option<&'a Arg> {
+    command
+        .get_arguments()
+        .filter(|arg| !COMMAND_LINE_ONLY.contains(&arg.get_id().as_str()))
+        .find(|arg| arg.get_long() == Some(key))
+}
+
+/// Whether `a` and `b` cannot be used together, declared on either side
+fn conflicts(command: &Command, a: &Arg, b: &Arg) -> bool {
+    command
+        .get_arg_conflicts_with(a)
+        .iter()
+        .any(|other| other.get_id() == b.get_id())
+        || command
+            .get_arg_conflicts_with(b)
+            .iter()
+            .any(|other| other.get_id() == a.get_id())
+}
+
+/// The arguments that set `arg` to a config `value`, in `--name=value` form
+fn arg_values(arg: &Arg, value: &Value) -> Result<Vec<OsString>, String> {
+    let long = arg.get_long().unwrap_or_default();
+    match (arg.get_action(), value) {
+        (ArgAction::SetTrue, Value::Boolean(true)) => Ok(vec![format!("--{}", long).into()]),
+        (ArgAction::SetTrue, Value::Boolean(false)) => Ok(Vec::new()),
+        (ArgAction::SetTrue, _) => Err("expected true or false".to_string()),
+        (ArgAction::Append, Value::Array(items)) => items
+            .iter()
+            .map(|item| Ok(format!("--{}={}", long, scalar(item)?).into()))
+            .collect(),
+        (_, value) => Ok(vec![format!("--{}={}", long, scalar(value)?).into()]),
+    }
+}
+
+/// A single TOML value as command-line text
+fn scalar(value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(text) => Ok(text.clone()),
+        Value::Integer(number) => Ok(number.to_string()),
+        Value::Float(number) => Ok(number.to_string()),
+        Value::Boolean(flag) => Ok(flag.to_string()),
+        Value::Datetime(datetime) => Ok(datetime.to_string()),
+        Value::Array(_) => Err("expected a single value, not a list".to_string()),
+        Value::Table(_) => Err("expected a value, not a table".to_string()),
+    }
+}
+
+/// Reports a parse error, adding which config file set the offending option
+fn exit_with_source(
+    err: clap::Error,
+    command: &Command,
+    sources: &HashMap<String, SettingSource>,
+) -> ! {
+    let _ = err.print();
+    if let Some(ContextValue::String(invalid)) = err.get(ContextKind::InvalidArg) {
+        let from_config = command.get_arguments().find_map(|arg| {
+            let long = format!("--{}", arg.get_long()?);
+            let starts_option = invalid == &long || invalid.starts_with(&format!("{} ", long));
+            let source = sources.get(arg.get_id().as_str())?;
+            starts_option.then_some((long, source))
+        });
+        if let Some((long, source)) = from_config {
+            eprintln!("Note: {} was set by {}", long, source);
+        }
+    }
+    process::exit(err.exit_code());
+}
+
+fn unknown_profile(name: &str, files: &[ConfigFile]) -> String {
+    let mut known: Vec<String> = files.iter().flat_map(ConfigFile::profile_names).collect();
+    known.sort();
+    known.dedup();
+    if known.is_empty() {
+        format!(
+            "Unknown profile '{}'; define it as [profiles.{}] in {} or {}",
+            name,
+            name,
+            config_path()
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|| "the user config".to_string()),
+            PROJECT_CONFIG_FILE
+        )
+    } else {
+        format!(
+            "Unknown profile '{}'; available profiles: {}",
+            name,
+            known.join(", ")
+        )
+    }
+}
+
+/// The effective value and source of every option that has a value
+fn settings(
+    command: &Command,
+    matches: &ArgMatches,
+    sources: &HashMap<String, SettingSource>,
+) -> Vec<Setting> {
+    command
+        .get_arguments()
+        .filter(|arg| !matches!(arg.get_id().as_str(), "help" | "version"))
+        .filter_map(|arg| {
+            let id = arg.get_id().as_str();
+            let values: Vec<String> = matches
+                .get_raw(id)?
+                .map(|value| value.to_string_lossy().into_owned())
+                .collect();
+            // Flags that are off are left out
+            if matches!(arg.get_action(), ArgAction::SetTrue) && values == ["false"] {
+                return None;
+            }
+            let source = match sources.get(id) {
+                Some(source) => source.clone(),
+                None if matches.value_source(id) == Some(ValueSource::CommandLine) => {
+                    SettingSource::CommandLine
+                }
+                None => SettingSource::Default,
+            };
+            Some(Setting {
+                name: arg.get_long().unwrap_or(id).to_string(),
+                value: values.join(","),
+                source,
+            })
+        })
+        .collect()
+}
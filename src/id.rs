@@ -1,15 +1,33 @@
+use crate::ksuid::Ksuid;
+use crate::monotonic::next_monotonic_ulid;
+use crate::namespace::parse_namespace_uuid;
+use crate::rng;
+use crate::snowflake::{next_snowflake, snowflake_at, SnowflakeConfig};
+use crate::typeid::TypeId;
+use crate::xid::Xid;
 use bson::oid::ObjectId;
 use cuid;
-use nanoid::nanoid;
-use std::str::FromStr;
+use sha3::{Digest, Sha3_512};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
 use ulid;
-use uuid::Uuid;
+use uuid::{Builder, Context, NoContext, Timestamp, Uuid};
 
 #[derive(Debug)]
 pub enum IDError {
     MissingNamespace(String),
     MissingName(String),
     InvalidNamespace(String),
+    InvalidUuid(String),
+    InvalidPayload(String),
+    InvalidSnowflake(String),
+    InvalidTypeId(String),
+    InvalidAlphabet(String),
+    InvalidTimestamp(String),
+    InvalidNode(String),
+    InvalidTemplate(String),
     // There are several potential CuidError states but all of them
     // seem to be caused by OS errors so I've just shimmed this for now
     CuidError(cuid::CuidError),
@@ -21,7 +39,15 @@ impl std::fmt::Display for IDError {
             IDError::MissingNamespace(msg) => write!(f, "{}", msg),
             IDError::MissingName(msg) => write!(f, "{}", msg),
             IDError::InvalidNamespace(msg) => write!(f, "{}", msg),
-            IDError::CuidError(err) => write!(f, "{}", err.to_string()), // This isn't great but should be fine
+            IDError::InvalidUuid(msg) => write!(f, "{}", msg),
+            IDError::InvalidPayload(msg) => write!(f, "{}", msg),
+            IDError::InvalidSnowflake(msg) => write!(f, "{}", msg),
+            IDError::InvalidTypeId(msg) => write!(f, "{}", msg),
+            IDError::InvalidAlphabet(msg) => write!(f, "{}", msg),
+            IDError::InvalidTimestamp(msg) => write!(f, "{}", msg),
+            IDError::InvalidNode(msg) => write!(f, "{}", msg),
+            IDError::InvalidTemplate(msg) => write!(f, "{}", msg),
+            IDError::CuidError(err) => write!(f, "{}", err), // This isn't great but should be fine
         }
     }
 }
@@ -35,18 +61,324 @@ pub enum IDFormat {
     Hyphenated(UuidVersion),
     URN(UuidVersion),
     OID,
-    NanoID,
+    NanoID(Option<NanoAlphabet>),
     Ulid,
+    /// ULIDs that sort strictly in generation order, even within a millisecond
+    MonotonicUlid,
     Cuid(CuidVersion),
+    Snowflake(SnowflakeConfig),
+    Ksuid,
+    TypeId(String),
+    Xid,
+}
+
+impl IDFormat {
+    /// Whether IDs of this format embed a creation timestamp
+    pub fn has_timestamp(&self) -> bool {
+        match self {
+            IDFormat::Simple(version) | IDFormat::Hyphenated(version) | IDFormat::URN(version) => {
+                matches!(
+                    version,
+                    UuidVersion::V1(_) | UuidVersion::V6(_) | UuidVersion::V7
+                )
+            }
+            IDFormat::NanoID(_) | IDFormat::Cuid(_) => false,
+            IDFormat::OID
+            | IDFormat::Ulid
+            | IDFormat::MonotonicUlid
+            | IDFormat::Snowflake(_)
+            | IDFormat::Ksuid
+            | IDFormat::TypeId(_)
+            | IDFormat::Xid => true,
+        }
+    }
 }
 
 /// Internal enum for UUID versions
 #[derive(Debug, Clone, Copy)]
 pub enum UuidVersion {
-    V1,
+    V1(UuidV1Fields),
     V3,
     V4,
     V5,
+    V6(UuidV1Fields),
+    V7,
+    V8(UuidV8Fields),
+}
+
+/// Node ID and clock sequence of a UUID v1 or v6 (RFC 9562, section 5.1)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UuidV1Fields {
+    /// 48-bit node ID. `None` uses a random node ID with the multicast bit set,
+    /// chosen once per process.
+    pub node: Option<[u8; 6]>,
+    /// 14-bit clock sequence. `None` starts from a random value.
+    pub clock_seq: Option<u16>,
+}
+
+impl UuidV1Fields {
+    /// Creates the fields, checking that the clock sequence fits in 14 bits
+    pub fn new(node: Option<[u8; 6]>, clock_seq: Option<u16>) -> Result<Self, IDError> {
+        if let Some(clock_seq) = clock_seq {
+            if clock_seq > 0x3FFF {
+                return Err(IDError::InvalidNode(format!(
+                    "UUID clock sequence must fit in 14 bits (0-16383), got {}",
+                    clock_seq
+                )));
+            }
+        }
+        Ok(UuidV1Fields { node, clock_seq })
+    }
+
+    /// Parses a node ID written as 12 hex digits, or as six two-digit groups
+    /// separated by `:` or `-`
+    pub fn parse_node(node: &str) -> Result<[u8; 6], IDError> {
+        let groups: Vec<&str> = match node.chars().find(|c| *c == ':' || *c == '-') {
+            Some(separator) => node.split(separator).collect(),
+            None => vec![node],
+        };
+        let well_formed = match groups.len() {
+            1 => node.len() == 12,
+            6 => groups.iter().all(|group| group.len() == 2),
+            _ => false,
+        };
+        let hex = groups.concat();
+        if !well_formed || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(IDError::InvalidNode(format!(
+                "Node ID must be 12 hex digits like 00:1a:2b:3c:4d:5e, got '{}'",
+                node
+            )));
+        }
+        let value = u64::from_str_radix(&hex, 16).unwrap();
+        let mut bytes = [0u8; 6];
+        bytes.copy_from_slice(&value.to_be_bytes()[2..]);
+        Ok(bytes)
+    }
+
+    /// Derives a node ID from the machine ID (`/etc/machine-id` on Linux, the
+    /// `MachineGuid` registry value on Windows, `IOPlatformUUID` on macOS), so it
+    /// stays the same across runs without exposing a MAC address. The machine ID
+    /// is hashed and the multicast bit is set, as RFC 9562 requires for non-MAC
+    /// node IDs.
+    pub fn machine_node() -> Result<[u8; 6], IDError> {
+        let machine_id = machine_id()?;
+        let hash = Uuid::new_v5(
+            &Uuid::NAMESPACE_OID,
+            format!("idgen/{}", machine_id).as_bytes(),
+        );
+        let mut node = [0u8; 6];
+        node.copy_from_slice(&hash.as_bytes()[..6]);
+        node[0] |= 0x01;
+        Ok(node)
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const MACHINE_ID_PATHS: &[&str] = &["/etc/machine-id", "/var/lib/dbus/machine-id"];
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn machine_id() -> Result<String, IDError> {
+    MACHINE_ID_PATHS
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .map(|contents| contents.trim().to_string())
+        .find(|id| !id.is_empty())
+        .ok_or_else(|| {
+            IDError::InvalidNode(format!(
+                "No machine ID found in {}",
+                MACHINE_ID_PATHS.join(" or ")
+            ))
+        })
+}
+
+#[cfg(target_os = "windows")]
+fn machine_id() -> Result<String, IDError> {
+    command_value(
+        "reg",
+        &[
+            "query",
+            r"HKLM\SOFTWARE\Microsoft\Cryptography",
+            "/v",
+            "MachineGuid",
+        ],
+        "MachineGuid",
+    )
+    .ok_or_else(|| {
+        IDError::InvalidNode(
+            "Cannot read the machine ID from the MachineGuid registry value".to_string(),
+        )
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn machine_id() -> Result<String, IDError> {
+    command_value(
+        "ioreg",
+        &["-rd1", "-c", "IOPlatformExpertDevice"],
+        "IOPlatformUUID",
+    )
+    .ok_or_else(|| {
+        IDError::InvalidNode(
+            "Cannot read the machine ID from the IOPlatformUUID in ioreg".to_string(),
+        )
+    })
+}
+
+/// Runs a command and returns the last word of the first output line naming
+/// `key`, without quotes. Reads `"IOPlatformUUID" = "..."` from ioreg and
+/// `MachineGuid    REG_SZ    ...` from reg.
+#[cfg_attr(not(any(target_os = "windows", target_os = "macos")), allow(dead_code))]
+fn command_value(program: &str, args: &[&str], key: &str) -> Option<String> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find(|line| line.contains(key))
+        .and_then(|line| line.split_whitespace().last())
+        .map(|value| value.trim_matches('"').to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Random node ID with the multicast bit set, generated once per process
+fn random_node() -> [u8; 6] {
+    static NODE: OnceLock<[u8; 6]> = OnceLock::new();
+    *NODE.get_or_init(|| {
+        let mut node: [u8; 6] = rng::random();
+        node[0] |= 0x01;
+        node
+    })
+}
+
+/// Custom fields of a UUID v8 (RFC 9562, section 5.8).
+/// Fields left as `None` are filled with random bits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UuidV8Fields {
+    /// 48-bit `custom_a` field (bits 0-47)
+    pub custom_a: Option<u64>,
+    /// 12-bit `custom_b` field (bits 52-63)
+    pub custom_b: Option<u64>,
+    /// 62-bit `custom_c` field (bits 66-127)
+    pub custom_c: Option<u64>,
+}
+
+impl UuidV8Fields {
+    /// Creates the fields, checking that every value fits its bit width
+    pub fn new(
+        custom_a: Option<u64>,
+        custom_b: Option<u64>,
+        custom_c: Option<u64>,
+    ) -> Result<Self, IDError> {
+        check_field_width("custom_a", custom_a, 48)?;
+        check_field_width("custom_b", custom_b, 12)?;
+        check_field_width("custom_c", custom_c, 62)?;
+        Ok(UuidV8Fields {
+            custom_a,
+            custom_b,
+            custom_c,
+        })
+    }
+
+    /// Parses a 128-bit hex payload (32 hex digits, hyphens allowed).
+    /// The version and variant bits of the payload are overwritten.
+    pub fn from_hex(payload: &str) -> Result<Self, IDError> {
+        let hex: String = payload.chars().filter(|c| *c != '-').collect();
+        // from_str_radix alone would also accept a leading '+'
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(IDError::InvalidPayload(format!(
+                "UUID v8 payload '{}' is not valid hex",
+                payload
+            )));
+        }
+        if hex.len() != 32 {
+            return Err(IDError::InvalidPayload(format!(
+                "UUID v8 payload must be 32 hex digits, got {}",
+                hex.len()
+            )));
+        }
+        let value = u128::from_str_radix(&hex, 16).unwrap();
+
+        Ok(UuidV8Fields {
+            custom_a: Some((value >> 80) as u64),
+            custom_b: Some((value >> 64) as u64 & 0x0FFF),
+            custom_c: Some(value as u64 & 0x3FFF_FFFF_FFFF_FFFF),
+        })
+    }
+}
+
+fn check_field_width(field: &str, value: Option<u64>, bits: u32) -> Result<(), IDError> {
+    match value {
+        Some(v) if v >> bits != 0 => Err(IDError::InvalidPayload(format!(
+            "UUID v8 {} must fit in {} bits, got {:#x}",
+            field, bits, v
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// A validated custom alphabet for NanoID: at least 2 unique, printable ASCII characters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NanoAlphabet(Vec<char>);
+
+impl NanoAlphabet {
+    /// Named alphabets accepted by `NanoAlphabet::preset`
+    pub const PRESETS: &'static [(&'static str, &'static str)] = &[
+        ("numbers", "0123456789"),
+        ("lowercase", "abcdefghijklmnopqrstuvwxyz"),
+        ("uppercase", "ABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+        (
+            "alphanumeric",
+            "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+        ),
+        // Excludes characters that are easy to confuse, such as 1/l/I and 0/O
+        (
+            "nolookalikes",
+            "346789ABCDEFGHJKLMNPQRTUVWXYabcdefghijkmnpqrtwxyz",
+        ),
+        ("hex", "0123456789abcdef"),
+    ];
+
+    /// Validates a custom alphabet
+    pub fn new(alphabet: &str) -> Result<Self, IDError> {
+        let chars: Vec<char> = alphabet.chars().collect();
+        // nanoid counts the output length in bytes, so multi-byte characters would never finish
+        if let Some(c) = chars.iter().find(|c| !c.is_ascii_graphic()) {
+            return Err(IDError::InvalidAlphabet(format!(
+                "NanoID alphabet must contain only printable ASCII characters, got {:?}",
+                c
+            )));
+        }
+        if chars.len() < 2 {
+            return Err(IDError::InvalidAlphabet(format!(
+                "NanoID alphabet must have at least 2 characters, got {}",
+                chars.len()
+            )));
+        }
+        for (i, c) in chars.iter().enumerate() {
+            if chars[..i].contains(c) {
+                return Err(IDError::InvalidAlphabet(format!(
+                    "NanoID alphabet must contain unique characters, '{}' appears more than once",
+                    c
+                )));
+            }
+        }
+        Ok(NanoAlphabet(chars))
+    }
+
+    /// Looks up a named alphabet such as `numbers` or `nolookalikes`
+    pub fn preset(name: &str) -> Option<Self> {
+        NanoAlphabet::PRESETS
+            .iter()
+            .find(|(preset, _)| preset.eq_ignore_ascii_case(name))
+            .map(|(_, alphabet)| NanoAlphabet(alphabet.chars().collect()))
+    }
+
+    /// The characters IDs are drawn from
+    pub fn chars(&self) -> &[char] {
+        &self.0
+    }
 }
 
 /// Internal enum for CUID versions
@@ -76,31 +408,256 @@ pub fn new_id(
     namespace: Option<&str>,
     name: Option<&str>,
 ) -> Result<String, IDError> {
+    new_id_at(id_format, len, namespace, name, None)
+}
+
+/**
+ * Returns a newly generated id stamped with the given time
+ *
+ * # Arguments
+ *
+ * * `id_format` - The format of the ID to generate
+ * * `len` - The length of the ID (only applicable for NanoID)
+ * * `namespace` - The namespace for UUID v3 and v5 (required for those versions)
+ * * `name` - The name for UUID v3 and v5 (required for those versions)
+ * * `at` - The time to embed instead of the current time. Only formats whose
+ *   `has_timestamp` is true accept it.
+ *
+ * # Returns
+ *
+ * A string representing the generated ID
+ */
+pub fn new_id_at(
+    id_format: &IDFormat,
+    len: Option<usize>,
+    namespace: Option<&str>,
+    name: Option<&str>,
+    at: Option<SystemTime>,
+) -> Result<String, IDError> {
+    if at.is_some() && !id_format.has_timestamp() {
+        return Err(IDError::InvalidTimestamp(
+            "This ID type does not embed a timestamp".to_string(),
+        ));
+    }
+
     match id_format {
-        IDFormat::Simple(version) => Ok(generate_uuid(*version, namespace, name)?
+        IDFormat::Simple(version) => Ok(generate_uuid(*version, namespace, name, at)?
             .simple()
             .to_string()),
-        IDFormat::Hyphenated(version) => Ok(generate_uuid(*version, namespace, name)?
+        IDFormat::Hyphenated(version) => Ok(generate_uuid(*version, namespace, name, at)?
             .hyphenated()
             .to_string()),
-        IDFormat::URN(version) => Ok(generate_uuid(*version, namespace, name)?.urn().to_string()),
-        IDFormat::OID => Ok(ObjectId::new().to_string()),
-        IDFormat::NanoID => {
+        IDFormat::URN(version) => Ok(generate_uuid(*version, namespace, name, at)?
+            .urn()
+            .to_string()),
+        IDFormat::OID if rng::is_seeded() => {
+            let seconds = object_id_seconds(at.unwrap_or_else(SystemTime::now))?;
+            Ok(seeded_object_id(seconds).to_string())
+        }
+        IDFormat::OID => match at {
+            Some(at) => {
+                let seconds = object_id_seconds(at)?;
+                // Keep the process value and counter of a fresh ObjectId
+                let mut bytes = ObjectId::new().bytes();
+                bytes[..4].copy_from_slice(&seconds.to_be_bytes());
+                Ok(ObjectId::from_bytes(bytes).to_string())
+            }
+            None => Ok(ObjectId::new().to_string()),
+        },
+        IDFormat::NanoID(alphabet) => {
             let l = len.unwrap_or(21);
-            Ok(nanoid!(l))
+            let alphabet = alphabet
+                .as_ref()
+                .map_or(&nanoid::alphabet::SAFE[..], |a| a.chars());
+            Ok(nanoid::format(rng::random_bytes, alphabet, l))
         }
         IDFormat::Cuid(version) => Ok(generate_cuid(*version))?,
-        IDFormat::Ulid => Ok(ulid::Ulid::new().to_string()),
+        IDFormat::Ulid => {
+            let time = match at {
+                Some(at) => check_48_bit_millis(at, "ULID")?,
+                None => SystemTime::now(),
+            };
+            Ok(rng::with_rng(|rng| ulid::Ulid::from_datetime_with_source(time, rng)).to_string())
+        }
+        IDFormat::MonotonicUlid => {
+            let at = at.map(|at| check_48_bit_millis(at, "ULID")).transpose()?;
+            Ok(next_monotonic_ulid(at)?.to_string())
+        }
+        IDFormat::Snowflake(config) => match at {
+            Some(at) => {
+                let (seconds, nanos) = unix_time(at)?;
+                let millis = seconds * 1000 + (nanos / 1_000_000) as u64;
+                Ok(snowflake_at(config, millis)?.to_string())
+            }
+            None => Ok(next_snowflake(config)?.to_string()),
+        },
+        IDFormat::Ksuid => match at {
+            Some(at) => {
+                let ksuid = Ksuid::from_unix(unix_time(at)?.0).ok_or_else(|| {
+                    IDError::InvalidTimestamp(
+                        "KSUID timestamps must be between 2014-05-13 and 2150-06-19".to_string(),
+                    )
+                })?;
+                Ok(ksuid.to_string())
+            }
+            None => Ok(Ksuid::new().to_string()),
+        },
+        IDFormat::TypeId(prefix) => match at {
+            Some(at) => {
+                let at = Some(check_48_bit_millis(at, "TypeID")?);
+                let uuid = generate_uuid(UuidVersion::V7, None, None, at)?;
+                Ok(TypeId::from_uuid(prefix, uuid)?.to_string())
+            }
+            None => Ok(TypeId::new(prefix)?.to_string()),
+        },
+        IDFormat::Xid => match at {
+            Some(at) => {
+                let xid = Xid::from_unix(unix_time(at)?.0).ok_or_else(|| {
+                    IDError::InvalidTimestamp(
+                        "xid timestamps must be before 2106-02-07".to_string(),
+                    )
+                })?;
+                Ok(xid.to_string())
+            }
+            None => Ok(Xid::new().to_string()),
+        },
     }
 }
 
+/// Splits a time into whole seconds and nanoseconds since the Unix epoch
+fn unix_time(at: SystemTime) -> Result<(u64, u32), IDError> {
+    at.duration_since(UNIX_EPOCH)
+        .map(|d| (d.as_secs(), d.subsec_nanos()))
+        .map_err(|_| {
+            IDError::InvalidTimestamp("Timestamps before 1970 are not supported".to_string())
+        })
+}
+
+/// Checks that a time fits the 32-bit second ObjectId timestamp
+fn object_id_seconds(at: SystemTime) -> Result<u32, IDError> {
+    u32::try_from(unix_time(at)?.0).map_err(|_| {
+        IDError::InvalidTimestamp("ObjectId timestamps must be before 2106-02-07".to_string())
+    })
+}
+
+/// Builds an ObjectId from the seeded PRNG: the 5-byte process value and the
+/// counter's starting point are drawn once, then the counter increments.
+fn seeded_object_id(seconds: u32) -> ObjectId {
+    static STATE: OnceLock<([u8; 5], AtomicU32)> = OnceLock::new();
+    let (process, counter) = STATE.get_or_init(|| (rng::random(), AtomicU32::new(rng::random())));
+    let count = counter.fetch_add(1, Ordering::Relaxed);
+
+    let mut bytes = [0u8; 12];
+    bytes[..4].copy_from_slice(&seconds.to_be_bytes());
+    bytes[4..9].copy_from_slice(process);
+    bytes[9..].copy_from_slice(&count.to_be_bytes()[1..]);
+    ObjectId::from_bytes(bytes)
+}
+
+/// Checks that a time fits a 48-bit millisecond timestamp, as used by ULIDs,
+/// UUID v7 and TypeIDs
+fn check_48_bit_millis(at: SystemTime, id_name: &str) -> Result<SystemTime, IDError> {
+    let (seconds, nanos) = unix_time(at)?;
+    let millis = seconds as u128 * 1000 + (nanos / 1_000_000) as u128;
+    if millis >= 1 << 48 {
+        return Err(IDError::InvalidTimestamp(format!(
+            "{} timestamps must be before 10889-08-02",
+            id_name
+        )));
+    }
+    Ok(at)
+}
+
+/// 100ns ticks issued for explicit times with a fixed clock sequence
+#[derive(Default)]
+struct ExplicitTicks {
+    used: HashSet<u64>,
+    /// Where to start looking for a free tick, for each requested tick
+    next: HashMap<u64, u64>,
+}
+
+impl ExplicitTicks {
+    /// The first unused tick at or after `ticks`
+    fn issue(&mut self, ticks: u64) -> u64 {
+        let start = self.next.get(&ticks).copied().unwrap_or(ticks);
+        let issued = (start..).find(|tick| !self.used.contains(tick)).unwrap();
+        self.used.insert(issued);
+        self.next.insert(ticks, issued + 1);
+        issued
+    }
+}
+
+/// Builds the timestamp of a UUID v1 or v6. Without an explicit clock sequence, a
+/// shared counter that starts at a random value keeps IDs for the same instant
+/// apart; for an explicit time the counter is kept per 100ns tick, and running
+/// out of its 16384 values is an error. A fixed clock sequence can't do that, so
+/// the time is nudged forward by 100ns ticks instead: the clock never goes back
+/// between IDs, and an explicit time moves to the first tick at or after it that
+/// this run hasn't used yet.
+fn v1_timestamp(fields: &UuidV1Fields, at: Option<SystemTime>) -> Result<Timestamp, IDError> {
+    static CONTEXT: OnceLock<Context> = OnceLock::new();
+    static LAST_TICKS: Mutex<u64> = Mutex::new(0);
+    static EXPLICIT_TICKS: OnceLock<Mutex<ExplicitTicks>> = OnceLock::new();
+    /// Clock sequences issued so far for each explicit tick
+    static EXPLICIT_CLOCK_SEQS: OnceLock<Mutex<HashMap<u64, u16>>> = OnceLock::new();
+
+    let (seconds, nanos) = unix_time(at.unwrap_or_else(SystemTime::now))?;
+    let ticks = seconds * 10_000_000 + (nanos / 100) as u64;
+    let (ticks, clock_seq) = match (fields.clock_seq, at) {
+        (Some(clock_seq), None) => {
+            let mut last = LAST_TICKS.lock().unwrap_or_else(|e| e.into_inner());
+            *last = ticks.max(*last + 1);
+            (*last, clock_seq)
+        }
+        (Some(clock_seq), Some(_)) => {
+            let mut explicit = EXPLICIT_TICKS
+                .get_or_init(Mutex::default)
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            (explicit.issue(ticks), clock_seq)
+        }
+        (None, None) => {
+            let context = CONTEXT.get_or_init(|| Context::new(rng::random()));
+            return Ok(Timestamp::from_unix(context, seconds, nanos));
+        }
+        (None, Some(_)) => {
+            static START: OnceLock<u16> = OnceLock::new();
+            let mut issued = EXPLICIT_CLOCK_SEQS
+                .get_or_init(Mutex::default)
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            let count = issued.entry(ticks).or_insert(0);
+            if *count > 0x3FFF {
+                return Err(IDError::InvalidTimestamp(
+                    "UUID clock sequence exhausted: at most 16384 IDs can share one 100ns timestamp"
+                        .to_string(),
+                ));
+            }
+            let start = *START.get_or_init(rng::random);
+            let clock_seq = start.wrapping_add(*count) & 0x3FFF;
+            *count += 1;
+            (ticks, clock_seq)
+        }
+    };
+    Ok(Timestamp::from_unix_time(
+        ticks / 10_000_000,
+        (ticks % 10_000_000) as u32 * 100,
+        clock_seq as u128,
+        14,
+    ))
+}
+
 fn generate_uuid(
     version: UuidVersion,
     namespace: Option<&str>,
     name: Option<&str>,
+    at: Option<SystemTime>,
 ) -> Result<Uuid, IDError> {
     match version {
-        UuidVersion::V1 => Ok(Uuid::now_v1(&[1, 2, 3, 4, 5, 6])),
+        UuidVersion::V1(fields) => Ok(Uuid::new_v1(
+            v1_timestamp(&fields, at)?,
+            &fields.node.unwrap_or_else(random_node),
+        )),
         UuidVersion::V3 => {
             let namespace = namespace.ok_or_else(||
                 IDError::MissingNamespace("UUID v3 requires --namespace parameter. Example: --namespace 6ba7b810-9dad-11d1-80b4-00c04fd430c8".to_string())
@@ -110,12 +667,10 @@ fn generate_uuid(
                     "UUID v3 requires --name parameter. Example: --name example.com".to_string(),
                 )
             })?;
-            let namespace = Uuid::from_str(namespace).map_err(|_|
-                IDError::InvalidNamespace("Invalid namespace UUID format. Must be a valid UUID like 6ba7b810-9dad-11d1-80b4-00c04fd430c8.\nCommon namespaces:\n  - DNS: 6ba7b810-9dad-11d1-80b4-00c04fd430c8\n  - URL: 6ba7b811-9dad-11d1-80b4-00c04fd430c8".to_string())
-            )?;
+            let namespace = parse_namespace_uuid(namespace)?;
             Ok(Uuid::new_v3(&namespace, name.as_bytes()))
         }
-        UuidVersion::V4 => Ok(Uuid::new_v4()),
+        UuidVersion::V4 => Ok(Builder::from_random_bytes(rng::random()).into_uuid()),
         UuidVersion::V5 => {
             let namespace = namespace.ok_or_else(||
                 IDError::MissingNamespace("UUID v5 requires --namespace parameter. Example: --namespace 6ba7b810-9dad-11d1-80b4-00c04fd430c8".to_string())
@@ -125,17 +680,156 @@ fn generate_uuid(
                     "UUID v5 requires --name parameter. Example: --name example.com".to_string(),
                 )
             })?;
-            let namespace = Uuid::from_str(namespace).map_err(|_|
-                IDError::InvalidNamespace("Invalid namespace UUID format. Must be a valid UUID like 6ba7b810-9dad-11d1-80b4-00c04fd430c8.\nCommon namespaces:\n  - DNS: 6ba7b810-9dad-11d1-80b4-00c04fd430c8\n  - URL: 6ba7b811-9dad-11d1-80b4-00c04fd430c8".to_string())
-            )?;
+            let namespace = parse_namespace_uuid(namespace)?;
             Ok(Uuid::new_v5(&namespace, name.as_bytes()))
         }
+        UuidVersion::V6(fields) => Ok(Uuid::new_v6(
+            v1_timestamp(&fields, at)?,
+            &fields.node.unwrap_or_else(random_node),
+        )),
+        UuidVersion::V7 => match at
+            .map(|at| check_48_bit_millis(at, "UUID v7"))
+            .transpose()?
+        {
+            Some(at) if rng::is_seeded() => {
+                let (seconds, nanos) = unix_time(at)?;
+                let millis = seconds * 1000 + (nanos / 1_000_000) as u64;
+                Ok(Builder::from_unix_timestamp_millis(millis, &rng::random()).into_uuid())
+            }
+            Some(at) => {
+                let (seconds, nanos) = unix_time(at)?;
+                Ok(Uuid::new_v7(Timestamp::from_unix(
+                    NoContext, seconds, nanos,
+                )))
+            }
+            None => Ok(Uuid::now_v7()),
+        },
+        UuidVersion::V8(fields) => {
+            let random: u128 = rng::random();
+            let custom_a = fields.custom_a.map_or(random >> 80, u128::from);
+            let custom_b = fields.custom_b.map_or(random >> 64, u128::from) & 0x0FFF;
+            let custom_c = fields.custom_c.map_or(random, u128::from) & 0x3FFF_FFFF_FFFF_FFFF;
+            let value = custom_a << 80 | custom_b << 64 | custom_c;
+            // new_v8 sets the version and variant bits over the payload
+            Ok(Uuid::new_v8(value.to_be_bytes()))
+        }
     }
 }
 
+/**
+ * Converts a UUID v1 into its v6 form
+ *
+ * The 60-bit timestamp is reordered most-significant bits first, while the
+ * clock sequence and node are carried over unchanged, so the conversion is
+ * lossless and can be reversed with `uuid_v6_to_v1`.
+ *
+ * # Arguments
+ *
+ * * `uuid` - A UUID v1
+ *
+ * # Returns
+ *
+ * The equivalent UUID v6
+ */
+pub fn uuid_v1_to_v6(uuid: &Uuid) -> Result<Uuid, IDError> {
+    if uuid.get_version_num() != 1 {
+        return Err(IDError::InvalidUuid(format!(
+            "Expected a UUID v1, got version {}",
+            uuid.get_version_num()
+        )));
+    }
+
+    let (time_low, time_mid, time_hi_and_version, clock_seq_and_node) = uuid.as_fields();
+    let ticks =
+        ((time_hi_and_version & 0x0FFF) as u64) << 48 | (time_mid as u64) << 32 | time_low as u64;
+
+    Ok(Uuid::from_fields(
+        (ticks >> 28) as u32,
+        (ticks >> 12) as u16,
+        0x6000 | (ticks & 0x0FFF) as u16,
+        clock_seq_and_node,
+    ))
+}
+
+/**
+ * Converts a UUID v6 back into its v1 form
+ *
+ * # Arguments
+ *
+ * * `uuid` - A UUID v6
+ *
+ * # Returns
+ *
+ * The equivalent UUID v1
+ */
+pub fn uuid_v6_to_v1(uuid: &Uuid) -> Result<Uuid, IDError> {
+    if uuid.get_version_num() != 6 {
+        return Err(IDError::InvalidUuid(format!(
+            "Expected a UUID v6, got version {}",
+            uuid.get_version_num()
+        )));
+    }
+
+    let (time_high, time_mid, time_low_and_version, clock_seq_and_node) = uuid.as_fields();
+    let ticks =
+        (time_high as u64) << 28 | (time_mid as u64) << 12 | (time_low_and_version & 0x0FFF) as u64;
+
+    Ok(Uuid::from_fields(
+        ticks as u32,
+        (ticks >> 32) as u16,
+        0x1000 | ((ticks >> 48) & 0x0FFF) as u16,
+        clock_seq_and_node,
+    ))
+}
+
 fn generate_cuid(version: CuidVersion) -> Result<String, IDError> {
     match version {
-        CuidVersion::V1 => cuid::cuid1().map_err(|err| IDError::CuidError(err)),
+        CuidVersion::V1 => cuid::cuid1().map_err(IDError::CuidError),
+        CuidVersion::V2 if rng::is_seeded() => Ok(seeded_cuid2()),
         CuidVersion::V2 => Ok(cuid::cuid2()),
     }
 }
+
+/// Builds a CUID2 the way the reference implementation does, but with every
+/// input drawn from the seeded PRNG. The time is left out of the hash so the
+/// output depends on the seed alone.
+fn seeded_cuid2() -> String {
+    const LENGTH: usize = 24;
+    const BASE36: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    static FINGERPRINT: OnceLock<String> = OnceLock::new();
+    static COUNTER: OnceLock<AtomicU64> = OnceLock::new();
+
+    let random_base36 = |len: usize| -> String {
+        (0..len)
+            .map(|_| BASE36[rng::random_range(0..36)] as char)
+            .collect()
+    };
+    let fingerprint = FINGERPRINT.get_or_init(|| random_base36(32));
+    let count = COUNTER
+        .get_or_init(|| AtomicU64::new(rng::random_range(0..476_782_367)))
+        .fetch_add(1, Ordering::Relaxed);
+
+    let mut hasher = Sha3_512::new();
+    hasher.update(random_base36(LENGTH));
+    hasher.update(count.to_string());
+    hasher.update(fingerprint);
+    let hash = hasher.finalize();
+
+    // Convert the 512-bit hash to base 36, most significant digit first
+    let mut digits = Vec::new();
+    let mut value = hash.to_vec();
+    while value.iter().any(|b| *b != 0) {
+        let mut remainder = 0u32;
+        for byte in value.iter_mut() {
+            let current = remainder << 8 | *byte as u32;
+            *byte = (current / 36) as u8;
+            remainder = current % 36;
+        }
+        digits.push(BASE36[remainder as usize] as char);
+    }
+
+    let mut id = String::with_capacity(LENGTH);
+    id.push(BASE36[rng::random_range(10..36)] as char);
+    id.extend(digits.iter().rev().take(LENGTH - 1));
+    id
+}
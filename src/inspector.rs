@@ -1,6 +1,10 @@
+use crate::ksuid::Ksuid;
+use crate::snowflake::SnowflakeConfig;
+use crate::typeid::TypeId;
+use crate::xid::Xid;
 use chrono::{DateTime, TimeZone, Utc};
 use regex::Regex;
-use serde::Serialize;
+use serde::{Serialize, Serializer};
 use uuid::Uuid;
 
 #[derive(Serialize, Debug)]
@@ -13,6 +17,19 @@ pub struct InspectionResult {
     pub timestamp: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub variant: Option<String>,
+    /// Type-specific fields decoded from the ID, in display order
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_components"
+    )]
+    pub components: Vec<(String, String)>,
+}
+
+fn serialize_components<S: Serializer>(
+    components: &[(String, String)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(components.iter().map(|(k, v)| (k, v)))
 }
 
 pub fn inspect_id(id: &str) -> InspectionResult {
@@ -21,24 +38,46 @@ pub fn inspect_id(id: &str) -> InspectionResult {
         let version = uuid.get_version().map(|v| format!("{:?}", v));
         let variant = format!("{:?}", uuid.get_variant());
 
-        // Extract timestamp for v1 and v7 (if supported by crate, v1 is standard)
-        // Note: uuid crate v1.0+ supports getting timestamp from v1, v6, v7
-        let timestamp = if let Some(uuid::Version::Mac) = uuid.get_version() {
-            // UUID v1 timestamp extraction is complex without direct crate support in older versions
-            // For now, we'll skip complex timestamp extraction for UUIDs to keep it simple
-            // unless we upgrade to uuid v1.0+ features explicitly.
-            // Actually, let's try a best effort for v1 if the crate allows,
-            // but the current uuid crate version in Cargo.toml is 1.18.1 which is good.
+        let timestamp = uuid_timestamp(&uuid);
 
-            // uuid 1.x exposes get_timestamp() which returns a Timestamp struct
-            uuid.get_timestamp().and_then(|ts| {
-                let (secs, nanos) = ts.to_unix();
-                Utc.timestamp_opt(secs as i64, nanos)
-                    .single()
-                    .map(|dt| dt.to_rfc3339())
-            })
-        } else {
-            None
+        let components = match uuid.get_version() {
+            // UUID v8 carries application-defined data, so show the raw custom fields
+            Some(uuid::Version::Custom) => {
+                let value = uuid.as_u128();
+                vec![
+                    ("custom_a".to_string(), format!("0x{:012x}", value >> 80)),
+                    (
+                        "custom_b".to_string(),
+                        format!("0x{:03x}", (value >> 64) & 0x0FFF),
+                    ),
+                    (
+                        "custom_c".to_string(),
+                        format!("0x{:016x}", value & 0x3FFF_FFFF_FFFF_FFFF),
+                    ),
+                ]
+            }
+            Some(uuid::Version::Mac) | Some(uuid::Version::SortMac) => {
+                let clock_seq =
+                    u16::from_be_bytes([uuid.as_bytes()[8], uuid.as_bytes()[9]]) & 0x3FFF;
+                let node = &uuid.as_bytes()[10..];
+                // A set multicast bit marks a random node ID rather than a MAC address
+                let kind = if node[0] & 0x01 != 0 { "random" } else { "MAC" };
+                vec![
+                    (
+                        "node".to_string(),
+                        format!(
+                            "{} ({})",
+                            node.iter()
+                                .map(|b| format!("{:02x}", b))
+                                .collect::<Vec<_>>()
+                                .join(":"),
+                            kind
+                        ),
+                    ),
+                    ("clock_seq".to_string(), clock_seq.to_string()),
+                ]
+            }
+            _ => Vec::new(),
         };
 
         return InspectionResult {
@@ -47,6 +86,7 @@ pub fn inspect_id(id: &str) -> InspectionResult {
             version,
             timestamp,
             variant: Some(variant),
+            components,
         };
     }
 
@@ -59,10 +99,30 @@ pub fn inspect_id(id: &str) -> InspectionResult {
             version: None,
             timestamp: Some(datetime.to_rfc3339()),
             variant: None,
+            components: Vec::new(),
         };
     }
 
-    // 3. Try MongoDB ObjectId (24 hex chars)
+    // 3. Try TypeID (prefix_suffix). A bare suffix is indistinguishable from a
+    // lowercase ULID, so only prefixed TypeIDs are detected.
+    if id.contains('_') {
+        if let Ok(typeid) = TypeId::parse(id) {
+            return InspectionResult {
+                valid: true,
+                id_type: "TypeID".to_string(),
+                version: typeid.uuid.get_version().map(|v| format!("{:?}", v)),
+                timestamp: uuid_timestamp(&typeid.uuid),
+                variant: None,
+                components: vec![
+                    ("prefix".to_string(), typeid.prefix.clone()),
+                    ("suffix".to_string(), typeid.suffix()),
+                    ("uuid".to_string(), typeid.uuid.to_string()),
+                ],
+            };
+        }
+    }
+
+    // 4. Try MongoDB ObjectId (24 hex chars)
     let object_id_regex = Regex::new(r"^[0-9a-fA-F]{24}$").unwrap();
     if object_id_regex.is_match(id) {
         // Extract timestamp (first 4 bytes / 8 hex chars)
@@ -74,11 +134,56 @@ pub fn inspect_id(id: &str) -> InspectionResult {
                 version: None,
                 timestamp: datetime.map(|dt| dt.to_rfc3339()),
                 variant: None,
+                components: Vec::new(),
+            };
+        }
+    }
+
+    // 5. Try xid (20 lowercase base32hex chars). An all-digit string is far more
+    // likely to be a plain integer, so require at least one letter.
+    if id.bytes().any(|b| b.is_ascii_lowercase()) {
+        if let Some(xid) = Xid::parse(id) {
+            return InspectionResult {
+                valid: true,
+                id_type: "xid".to_string(),
+                version: None,
+                timestamp: Utc
+                    .timestamp_opt(xid.timestamp as i64, 0)
+                    .single()
+                    .map(|dt| dt.to_rfc3339()),
+                variant: None,
+                components: vec![
+                    (
+                        "machine_id".to_string(),
+                        xid.machine_id
+                            .iter()
+                            .map(|b| format!("{:02x}", b))
+                            .collect(),
+                    ),
+                    ("pid".to_string(), xid.pid.to_string()),
+                    ("counter".to_string(), xid.counter.to_string()),
+                ],
             };
         }
     }
 
-    // 4. Try CUID (v1 starts with 'c', v2 is 24 chars usually)
+    // 6. Try KSUID (27 base62 chars: 4-byte timestamp + 16-byte payload)
+    if let Some(ksuid) = Ksuid::parse(id) {
+        let payload: String = ksuid.payload.iter().map(|b| format!("{:02x}", b)).collect();
+        return InspectionResult {
+            valid: true,
+            id_type: "KSUID".to_string(),
+            version: None,
+            timestamp: Utc
+                .timestamp_opt(ksuid.unix_timestamp(), 0)
+                .single()
+                .map(|dt| dt.to_rfc3339()),
+            variant: None,
+            components: vec![("payload".to_string(), payload)],
+        };
+    }
+
+    // 7. Try CUID (v1 starts with 'c', v2 is 24 chars usually)
     // CUID v1
     if id.starts_with('c') && id.len() >= 25 {
         return InspectionResult {
@@ -87,6 +192,7 @@ pub fn inspect_id(id: &str) -> InspectionResult {
             version: Some("v1".to_string()),
             timestamp: None, // CUID v1 timestamp is base36 encoded, doable but custom logic
             variant: None,
+            components: Vec::new(),
         };
     }
 
@@ -106,10 +212,11 @@ pub fn inspect_id(id: &str) -> InspectionResult {
             version: Some("v2".to_string()),
             timestamp: None,
             variant: None,
+            components: Vec::new(),
         };
     }
 
-    // 5. NanoID (Hard to detect definitively as it's just random chars)
+    // 8. NanoID (Hard to detect definitively as it's just random chars)
     // We can just check for URL-safe chars and length
     let nanoid_regex = Regex::new(r"^[A-Za-z0-9_-]{21}$").unwrap();
     if nanoid_regex.is_match(id) {
@@ -119,6 +226,7 @@ pub fn inspect_id(id: &str) -> InspectionResult {
             version: None,
             timestamp: None,
             variant: None,
+            components: Vec::new(),
         };
     }
 
@@ -128,5 +236,61 @@ pub fn inspect_id(id: &str) -> InspectionResult {
         version: None,
         timestamp: None,
         variant: None,
+        components: Vec::new(),
+    }
+}
+
+/// Extracts the embedded timestamp for time-based UUID versions (v1, v6 and v7)
+fn uuid_timestamp(uuid: &Uuid) -> Option<String> {
+    match uuid.get_version() {
+        // uuid 1.x exposes get_timestamp() which returns a Timestamp struct
+        Some(uuid::Version::Mac) | Some(uuid::Version::SortMac) | Some(uuid::Version::SortRand) => {
+            uuid.get_timestamp().and_then(|ts| {
+                let (secs, nanos) = ts.to_unix();
+                Utc.timestamp_opt(secs as i64, nanos)
+                    .single()
+                    .map(|dt| dt.to_rfc3339())
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Decodes `id` as a Snowflake ID with the given layout.
+/// Snowflakes are plain integers, so they are never auto-detected by `inspect_id`.
+pub fn inspect_snowflake(id: &str, config: &SnowflakeConfig) -> InspectionResult {
+    let parts = match id.parse::<u64>() {
+        Ok(value) => config.decode(value),
+        Err(_) => {
+            return InspectionResult {
+                valid: false,
+                id_type: "Unknown".to_string(),
+                version: None,
+                timestamp: None,
+                variant: None,
+                components: Vec::new(),
+            }
+        }
+    };
+
+    let mut components = Vec::new();
+    if config.datacenter_bits > 0 {
+        components.push(("datacenter_id".to_string(), parts.datacenter_id.to_string()));
+    }
+    if config.worker_bits > 0 {
+        components.push(("worker_id".to_string(), parts.worker_id.to_string()));
+    }
+    components.push(("sequence".to_string(), parts.sequence.to_string()));
+
+    InspectionResult {
+        valid: true,
+        id_type: "Snowflake".to_string(),
+        version: None,
+        timestamp: Utc
+            .timestamp_millis_opt(parts.timestamp as i64)
+            .single()
+            .map(|dt| dt.to_rfc3339()),
+        variant: None,
+        components,
     }
 }
@@ -0,0 +1,105 @@
+use crate::rng;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// KSUID timestamps count seconds from 2014-05-13T16:53:20Z
+pub const KSUID_EPOCH: i64 = 1_400_000_000;
+
+/// Length of the base62 string form
+pub const KSUID_LENGTH: usize = 27;
+
+const BASE62: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// A K-Sortable Unique IDentifier: a 32-bit timestamp followed by a 128-bit payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ksuid {
+    /// Seconds since `KSUID_EPOCH`
+    pub timestamp: u32,
+    pub payload: [u8; 16],
+}
+
+impl Ksuid {
+    /// Creates a KSUID for the current time with a random payload
+    pub fn new() -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(KSUID_EPOCH);
+        Ksuid {
+            timestamp: (now - KSUID_EPOCH) as u32,
+            payload: rng::random(),
+        }
+    }
+
+    /// Creates a KSUID for the given Unix time (in seconds) with a random payload.
+    /// Returns `None` if the time falls outside the KSUID range.
+    pub fn from_unix(seconds: u64) -> Option<Self> {
+        let timestamp = (seconds as i64).checked_sub(KSUID_EPOCH)?;
+        Some(Ksuid {
+            timestamp: u32::try_from(timestamp).ok()?,
+            payload: rng::random(),
+        })
+    }
+
+    /// Seconds since the Unix epoch
+    pub fn unix_timestamp(&self) -> i64 {
+        self.timestamp as i64 + KSUID_EPOCH
+    }
+
+    /// Parses the 27-character base62 form, rejecting values above 2^160 - 1
+    pub fn parse(s: &str) -> Option<Self> {
+        if s.len() != KSUID_LENGTH {
+            return None;
+        }
+
+        // Accumulate base62 digits into a 160-bit big-endian number
+        let mut bytes = [0u8; 20];
+        for c in s.bytes() {
+            let digit = BASE62.iter().position(|&b| b == c)? as u32;
+            let mut carry = digit;
+            for byte in bytes.iter_mut().rev() {
+                let value = *byte as u32 * 62 + carry;
+                *byte = value as u8;
+                carry = value >> 8;
+            }
+            if carry != 0 {
+                return None;
+            }
+        }
+
+        let mut payload = [0u8; 16];
+        payload.copy_from_slice(&bytes[4..]);
+        Some(Ksuid {
+            timestamp: u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            payload,
+        })
+    }
+}
+
+impl Default for Ksuid {
+    fn default() -> Self {
+        Ksuid::new()
+    }
+}
+
+impl fmt::Display for Ksuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bytes = [0u8; 20];
+        bytes[..4].copy_from_slice(&self.timestamp.to_be_bytes());
+        bytes[4..].copy_from_slice(&self.payload);
+
+        // Repeatedly divide the 160-bit number by 62, collecting remainders
+        let mut digits = [b'0'; KSUID_LENGTH];
+        for digit in digits.iter_mut().rev() {
+            let mut remainder = 0u32;
+            for byte in bytes.iter_mut() {
+                let value = (remainder << 8) | *byte as u32;
+                *byte = (value / 62) as u8;
+                remainder = value % 62;
+            }
+            *digit = BASE62[remainder as usize];
+        }
+
+        f.write_str(std::str::from_utf8(&digits).unwrap())
+    }
+}
@@ -1,5 +1,16 @@
 pub mod cli;
+pub mod config;
 pub mod id;
 pub mod inspector;
+pub mod ksuid;
+pub mod monotonic;
+pub mod namespace;
+pub mod output;
 pub mod processor;
 pub mod processor_clap;
+pub mod rng;
+pub mod snowflake;
+pub mod stream;
+pub mod template;
+pub mod typeid;
+pub mod xid;
@@ -0,0 +1,53 @@
+use crate::id::IDError;
+use crate::rng;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use ulid::Ulid;
+
+/// Last issued monotonic ULID, shared by all generators in the process
+static LAST_ULID: Mutex<Option<Ulid>> = Mutex::new(None);
+
+/**
+ * Returns the next monotonic ULID
+ *
+ * Within a millisecond (or if the clock goes backwards), the previous ULID's
+ * random component is incremented instead of drawing a new one, so every ID
+ * sorts strictly after the one before it.
+ *
+ * # Arguments
+ *
+ * * `at` - The time to embed instead of the current time
+ */
+pub fn next_monotonic_ulid(at: Option<SystemTime>) -> Result<Ulid, IDError> {
+    let time = at.unwrap_or_else(SystemTime::now);
+    let fresh = rng::with_rng(|rng| Ulid::from_datetime_with_source(time, rng));
+
+    let mut last = LAST_ULID.lock().unwrap_or_else(|e| e.into_inner());
+    let next = match *last {
+        Some(previous) if previous.timestamp_ms() >= fresh.timestamp_ms() => {
+            previous.increment().ok_or_else(|| {
+                IDError::InvalidTimestamp(format!(
+                    "Monotonic ULID random component overflowed at timestamp {}",
+                    previous.timestamp_ms()
+                ))
+            })?
+        }
+        _ => fresh,
+    };
+    *last = Some(next);
+    Ok(next)
+}
+
+/// Continues a monotonic sequence from a ULID issued earlier, such as one read
+/// from a state file. Has no effect if a later ULID was already issued.
+pub fn resume_monotonic_ulid(previous: Ulid) {
+    let mut last = LAST_ULID.lock().unwrap_or_else(|e| e.into_inner());
+    if last.is_none_or(|last| last < previous) {
+        *last = Some(previous);
+    }
+}
+
+/// The most recently issued monotonic ULID, if any
+pub fn last_monotonic_ulid() -> Option<Ulid> {
+    *LAST_ULID.lock().unwrap_or_else(|e| e.into_inner())
+}
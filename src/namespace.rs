@@ -0,0 +1,272 @@
+use crate::id::IDError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Namespaces predefined by RFC 9562, always available
+pub const BUILTIN_NAMESPACES: &[(&str, Uuid)] = &[
+    ("DNS", Uuid::NAMESPACE_DNS),
+    ("URL", Uuid::NAMESPACE_URL),
+    ("OID", Uuid::NAMESPACE_OID),
+    ("X500", Uuid::NAMESPACE_X500),
+];
+
+/// Where a namespace was defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NamespaceSource {
+    BuiltIn,
+    Config,
+    CommandLine,
+}
+
+impl fmt::Display for NamespaceSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NamespaceSource::BuiltIn => write!(f, "built-in"),
+            NamespaceSource::Config => write!(f, "config"),
+            NamespaceSource::CommandLine => write!(f, "command-line"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NamespaceEntry {
+    pub name: String,
+    pub uuid: Uuid,
+    pub source: NamespaceSource,
+}
+
+/// The `[namespaces]` table of the config file
+#[derive(Deserialize, Default)]
+struct NamespaceConfig {
+    #[serde(default)]
+    namespaces: BTreeMap<String, String>,
+}
+
+/// Built-in namespaces plus the custom ones from the config file and command line.
+/// Names are matched case-insensitively.
+#[derive(Debug, Default)]
+pub struct NamespaceRegistry {
+    custom: Vec<NamespaceEntry>,
+}
+
+impl NamespaceRegistry {
+    pub fn new() -> Self {
+        NamespaceRegistry::default()
+    }
+
+    /// Adds the namespaces from the `[namespaces]` table of a TOML config file.
+    /// A missing file is not an error.
+    pub fn load_config(&mut self, path: &Path) -> Result<(), IDError> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => {
+                return Err(IDError::InvalidNamespace(format!(
+                    "Cannot read config file '{}': {}",
+                    path.display(),
+                    err
+                )))
+            }
+        };
+        let config: NamespaceConfig = toml::from_str(&contents).map_err(|err| {
+            IDError::InvalidNamespace(format!("Invalid config file '{}': {}", path.display(), err))
+        })?;
+
+        for (name, uuid) in &config.namespaces {
+            let uuid = parse_namespace_uuid(uuid).map_err(|err| {
+                IDError::InvalidNamespace(format!(
+                    "Namespace '{}' in '{}': {}",
+                    name,
+                    path.display(),
+                    err
+                ))
+            })?;
+            self.define(name, uuid, NamespaceSource::Config)?;
+        }
+        Ok(())
+    }
+
+    /// Adds a namespace given as `NAME=UUID`
+    pub fn define_from_arg(&mut self, definition: &str) -> Result<(), IDError> {
+        let (name, uuid) = definition.split_once('=').ok_or_else(|| {
+            IDError::InvalidNamespace(format!(
+                "Namespace definition '{}' must look like NAME=UUID",
+                definition
+            ))
+        })?;
+        let uuid = parse_namespace_uuid(uuid)?;
+        self.define(name, uuid, NamespaceSource::CommandLine)
+    }
+
+    /// Adds a custom namespace, replacing an earlier custom one with the same name
+    pub fn define(
+        &mut self,
+        name: &str,
+        uuid: Uuid,
+        source: NamespaceSource,
+    ) -> Result<(), IDError> {
+        validate_name(name)?;
+        if BUILTIN_NAMESPACES
+            .iter()
+            .any(|(builtin, _)| builtin.eq_ignore_ascii_case(name))
+        {
+            return Err(IDError::InvalidNamespace(format!(
+                "Namespace '{}' is built in and cannot be redefined",
+                name
+            )));
+        }
+
+        let entry = NamespaceEntry {
+            name: name.to_string(),
+            uuid,
+            source,
+        };
+        match self
+            .custom
+            .iter_mut()
+            .find(|existing| existing.name.eq_ignore_ascii_case(name))
+        {
+            Some(existing) => *existing = entry,
+            None => self.custom.push(entry),
+        }
+        Ok(())
+    }
+
+    /// Looks up a built-in or custom namespace by name
+    pub fn get(&self, name: &str) -> Option<Uuid> {
+        BUILTIN_NAMESPACES
+            .iter()
+            .find(|(builtin, _)| builtin.eq_ignore_ascii_case(name))
+            .map(|(_, uuid)| *uuid)
+            .or_else(|| {
+                self.custom
+                    .iter()
+                    .find(|entry| entry.name.eq_ignore_ascii_case(name))
+                    .map(|entry| entry.uuid)
+            })
+    }
+
+    /// All namespaces, built-in ones first
+    pub fn entries(&self) -> Vec<NamespaceEntry> {
+        BUILTIN_NAMESPACES
+            .iter()
+            .map(|(name, uuid)| NamespaceEntry {
+                name: name.to_string(),
+                uuid: *uuid,
+                source: NamespaceSource::BuiltIn,
+            })
+            .chain(self.custom.iter().cloned())
+            .collect()
+    }
+}
+
+/// Namespace names are limited to letters, digits, '-' and '_'
+fn validate_name(name: &str) -> Result<(), IDError> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(IDError::InvalidNamespace(format!(
+            "Namespace name '{}' must be non-empty and use only letters, digits, '-' and '_'",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Digits in each group of a hyphenated UUID
+const GROUP_LENGTHS: [usize; 5] = [8, 4, 4, 4, 12];
+
+/**
+ * Parses a namespace UUID, reporting exactly what is wrong with a malformed one
+ *
+ * Accepts hyphenated (`6ba7b810-9dad-11d1-80b4-00c04fd430c8`), simple
+ * (`6ba7b8109dad11d180b400c04fd430c8`), braced (`{...}`) and URN
+ * (`urn:uuid:...`) forms, in either case.
+ *
+ * # Arguments
+ *
+ * * `input` - The UUID text
+ *
+ * # Returns
+ *
+ * The UUID, or `IDError::InvalidNamespace` naming the offending character
+ * (with its 1-based position) or group
+ */
+pub fn parse_namespace_uuid(input: &str) -> Result<Uuid, IDError> {
+    let invalid = |detail: String| {
+        IDError::InvalidNamespace(format!("Invalid namespace UUID '{}': {}", input, detail))
+    };
+
+    let (body, offset) = if let Some(rest) = input.strip_prefix('{') {
+        let body = rest
+            .strip_suffix('}')
+            .ok_or_else(|| invalid("missing closing '}'".to_string()))?;
+        (body, 1)
+    } else if input
+        .get(..9)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("urn:uuid:"))
+    {
+        (&input[9..], 9)
+    } else {
+        (input, 0)
+    };
+
+    // Report the first character that is neither a hex digit nor a group separator
+    if let Some((index, c)) = body
+        .char_indices()
+        .find(|(_, c)| !c.is_ascii_hexdigit() && *c != '-')
+    {
+        let position = input[..offset + index].chars().count() + 1;
+        return Err(invalid(format!(
+            "invalid character '{}' at position {}",
+            c, position
+        )));
+    }
+
+    if body.contains('-') {
+        let groups: Vec<&str> = body.split('-').collect();
+        if groups.len() != GROUP_LENGTHS.len() {
+            return Err(invalid(format!(
+                "expected 5 hyphen-separated groups (8-4-4-4-12), found {}",
+                groups.len()
+            )));
+        }
+        for (i, (group, expected)) in groups.iter().zip(GROUP_LENGTHS).enumerate() {
+            if group.len() != expected {
+                return Err(invalid(format!(
+                    "group {} has {} hex digits, expected {}",
+                    i + 1,
+                    group.len(),
+                    expected
+                )));
+            }
+        }
+    } else if body.len() != 32 {
+        return Err(invalid(format!(
+            "expected 32 hex digits, found {}",
+            body.len()
+        )));
+    }
+
+    let hex: String = body.chars().filter(|c| *c != '-').collect();
+    let value = u128::from_str_radix(&hex, 16).map_err(|err| invalid(err.to_string()))?;
+    Ok(Uuid::from_u128(value))
+}
+
+/// `$XDG_CONFIG_HOME/idgen/config.toml`, falling back to `~/.config/idgen/config.toml`
+pub fn config_path() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_home.join("idgen").join("config.toml"))
+}
@@ -0,0 +1,176 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, StdoutLock, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Buffer size for stdout and output files
+pub const OUTPUT_BUFFER_SIZE: usize = 1 << 20;
+
+/**
+ * A file that is written under a temporary name and renamed into place by
+ * `commit`, so readers never see it half written
+ *
+ * The temporary file sits next to the target, so the rename stays on one
+ * filesystem. It is removed if the `AtomicFile` is dropped without a commit.
+ */
+pub struct AtomicFile {
+    path: PathBuf,
+    temp_path: PathBuf,
+    file: Option<BufWriter<File>>,
+}
+
+impl AtomicFile {
+    /**
+     * Starts writing a file that will replace `path` on commit
+     *
+     * # Arguments
+     *
+     * * `path` - The final location of the file
+     */
+    pub fn create(path: &Path) -> io::Result<Self> {
+        let file_name = path.file_name().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("'{}' is not a file path", path.display()),
+            )
+        })?;
+        let temp_path = path.with_file_name(format!(
+            ".{}.{}.tmp",
+            file_name.to_string_lossy(),
+            process::id()
+        ));
+        let file = File::create(&temp_path)?;
+        Ok(AtomicFile {
+            path: path.to_path_buf(),
+            temp_path,
+            file: Some(BufWriter::with_capacity(OUTPUT_BUFFER_SIZE, file)),
+        })
+    }
+
+    /// Flushes the file to disk and renames it into place
+    pub fn commit(mut self) -> io::Result<()> {
+        let file = self.file.take().expect("file is only taken on commit");
+        let result = file
+            .into_inner()
+            .map_err(|err| err.into_error())
+            .and_then(|file| file.sync_all())
+            .and_then(|_| fs::rename(&self.temp_path, &self.path));
+        if result.is_err() {
+            let _ = fs::remove_file(&self.temp_path);
+        }
+        result
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.as_mut().expect("not committed").write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.as_mut().expect("not committed").flush()
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if self.file.take().is_some() {
+            let _ = fs::remove_file(&self.temp_path);
+        }
+    }
+}
+
+/// Where generated output goes
+pub enum Output {
+    Stdout(BufWriter<StdoutLock<'static>>),
+    /// A new or replaced file, renamed into place once complete
+    File(AtomicFile),
+    /// An existing file written in place with --append
+    Append(BufWriter<File>),
+}
+
+impl Output {
+    /// Buffered stdout
+    pub fn stdout() -> Self {
+        Output::Stdout(BufWriter::with_capacity(
+            OUTPUT_BUFFER_SIZE,
+            io::stdout().lock(),
+        ))
+    }
+
+    /**
+     * Opens an output file
+     *
+     * # Arguments
+     *
+     * * `path` - The file to write
+     * * `append` - Add to the end of the file instead of replacing it
+     */
+    pub fn file(path: &Path, append: bool) -> io::Result<Self> {
+        if append {
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            Ok(Output::Append(BufWriter::with_capacity(
+                OUTPUT_BUFFER_SIZE,
+                file,
+            )))
+        } else {
+            AtomicFile::create(path).map(Output::File)
+        }
+    }
+
+    /// Whether the output already has content, so a header would repeat
+    pub fn has_content(&self) -> io::Result<bool> {
+        match self {
+            Output::Append(file) => Ok(file.get_ref().metadata()?.len() > 0),
+            _ => Ok(false),
+        }
+    }
+
+    /// Flushes the output, renaming a new file into place
+    pub fn commit(self) -> io::Result<()> {
+        match self {
+            Output::Stdout(mut out) => out.flush(),
+            Output::File(file) => file.commit(),
+            Output::Append(mut file) => file.flush(),
+        }
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Output::Stdout(out) => out.write(buf),
+            Output::File(file) => file.write(buf),
+            Output::Append(file) => file.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Output::Stdout(out) => out.flush(),
+            Output::File(file) => file.flush(),
+            Output::Append(file) => file.flush(),
+        }
+    }
+}
+
+/**
+ * Names part `part` (from 1) of a split output, such as `ids-0001.txt` for
+ * `ids.txt`
+ *
+ * # Arguments
+ *
+ * * `path` - The --output path
+ * * `part` - The part number, padded to four digits
+ */
+pub fn split_path(path: &Path, part: u64) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = match path.extension() {
+        Some(extension) => format!("{}-{:04}.{}", stem, part, extension.to_string_lossy()),
+        None => format!("{}-{:04}", stem, part),
+    };
+    path.with_file_name(name)
+}
@@ -1,4 +1,4 @@
-use crate::id::{new_id, CuidVersion, IDError, IDFormat, UuidVersion};
+use crate::id::{new_id, CuidVersion, IDError, IDFormat, UuidV1Fields, UuidVersion};
 use crate::inspector::inspect_id;
 use serde::Serialize;
 use std::env;
@@ -37,7 +37,7 @@ pub fn parse_n_process() {
 
     let mut lastcmd = String::new();
 
-    args.iter().enumerate().for_each(|(_, arg)| {
+    args.iter().for_each(|arg| {
         if arg == "-h" || arg == "--help" {
             help = true;
         } else if arg == "-v" || arg == "--version" {
@@ -52,7 +52,7 @@ pub fn parse_n_process() {
         } else if arg == "-o" || arg == "--objectid" {
             format = IDFormat::OID;
         } else if arg == "-n" || arg == "--nano" {
-            format = IDFormat::NanoID;
+            format = IDFormat::NanoID(None);
         } else if arg == "-c1" || arg == "--cuid1" {
             format = IDFormat::Cuid(CuidVersion::V1);
         } else if arg == "-c2" || arg == "--cuid2" {
@@ -62,7 +62,7 @@ pub fn parse_n_process() {
         } else if arg == "-b" || arg == "--banner" {
             show_banner = true;
         } else if arg == "-u1" || arg == "--uuid1" {
-            version = UuidVersion::V1;
+            version = UuidVersion::V1(UuidV1Fields::default());
             format = match format.clone() {
                 IDFormat::Simple(_) => IDFormat::Simple(version),
                 IDFormat::Hyphenated(_) => IDFormat::Hyphenated(version),
@@ -184,6 +184,7 @@ pub fn parse_n_process() {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn print_uuid(
     id_format: IDFormat,
     len: Option<usize>,
@@ -209,23 +210,23 @@ fn print_uuid(
             let id = new_id(&id_format, len, namespace, name)?;
             print!("{}{}{}", prefix, id, suffix);
             if i < count - 1 {
-                print!("\n");
+                println!();
             }
         }
-        print!("\n");
+        println!();
     }
     Ok(())
 }
 
 /// Prints the program version.
 fn print_version() {
-    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+    const VERSION: &str = env!("CARGO_PKG_VERSION");
     print!("Version {}", VERSION);
 }
 
 /// Prints the help message!
 fn print_help() {
-    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+    const VERSION: &str = env!("CARGO_PKG_VERSION");
     let help = format!(
         "ID Generator Version {}
   Mohamed Aamir Maniar - https://www.linkedin.com/in/aamironline/
@@ -295,5 +296,5 @@ fn print_banner() {
 | | (_| | (_| |  __/ | | |
 |_|\__,_|\__, |\___|_| |_|
          |___/"#;
-    print!("{}\n", banner); // Changed to print! and explicit \n
+    println!("{}", banner);
 }
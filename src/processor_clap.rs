@@ -1,12 +1,43 @@
-use crate::cli::{build_cli, resolve_namespace, Cli, Commands, IdType, UuidFormat};
-use crate::id::{new_id, CuidVersion, IDError, IDFormat, UuidVersion};
-use crate::inspector::inspect_id;
-use clap::Parser;
+use crate::cli::{
+    build_cli, resolve_namespace_path, Cli, Column, Commands, ConfigAction, IdType, OutputFormat,
+    SnowflakePreset, Spread, SqidsArgs, UuidFormat,
+};
+use crate::config::{self, config_files, config_paths, Setting};
+use crate::id::{
+    new_id, new_id_at, uuid_v1_to_v6, uuid_v6_to_v1, CuidVersion, IDError, IDFormat, NanoAlphabet,
+    UuidV1Fields, UuidV8Fields, UuidVersion,
+};
+use crate::inspector::{inspect_id, inspect_snowflake, InspectionResult};
+use crate::monotonic::{last_monotonic_ulid, resume_monotonic_ulid};
+use crate::namespace::NamespaceRegistry;
+use crate::output::{split_path, AtomicFile, Output};
+use crate::rng;
+use crate::snowflake::SnowflakeConfig;
+use crate::stream::{self, Stream};
+use crate::template::{IdPlaceholder, Template};
+use crate::typeid::validate_prefix;
+use clap::ValueEnum;
 use clap_complete::generate;
 use clap_mangen::Man;
 use serde::Serialize;
-use std::io;
+use sqids::Sqids;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::ops::Range;
+use std::path::Path;
 use std::process;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use ulid::Ulid;
+use uuid::Uuid;
+
+/// IDs per unit of work handed to a --threads worker
+const CHUNK_SIZE: u64 = 8192;
+
+/// Errors that can be passed back from --threads workers
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
 
 /// Exit codes following Unix conventions
 pub mod exit_codes {
@@ -21,16 +52,137 @@ pub mod exit_codes {
 #[derive(Serialize)]
 struct IdOutput {
     value: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    id_type: Option<String>,
+    /// `Some(None)` when the timestamp column was asked for but the ID has none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<Option<String>>,
+}
+
+#[derive(Serialize)]
+struct NameOutput<'a> {
+    name: &'a str,
+    uuid: String,
+}
+
+#[derive(Serialize)]
+struct SqidsOutput {
+    value: String,
+    numbers: Vec<u64>,
+}
+
+/// Where generated IDs come from: a single ID type, or a --template
+enum Generator {
+    Id {
+        format: IDFormat,
+        namespace: Option<String>,
+        name: Option<String>,
+    },
+    Template(Template),
+}
+
+impl Generator {
+    /// The type name for the `type` column
+    fn type_name(&self, cli: &Cli) -> String {
+        match self {
+            Generator::Id { .. } => cli
+                .id_type
+                .to_possible_value()
+                .unwrap()
+                .get_name()
+                .to_string(),
+            Generator::Template(_) => "template".to_string(),
+        }
+    }
+
+    /// The timestamp embedded in `id`, read back with the inspector
+    fn timestamp(&self, id: &str) -> Option<String> {
+        match self {
+            Generator::Id {
+                format: IDFormat::Snowflake(config),
+                ..
+            } => inspect_snowflake(id, config).timestamp,
+            Generator::Id { format, .. } if format.has_timestamp() => inspect_id(id).timestamp,
+            _ => None,
+        }
+    }
+
+    /// Generates the ID at position `index` (from 0)
+    fn generate(&self, cli: &Cli, index: u64, at: Option<SystemTime>) -> Result<String, IDError> {
+        match self {
+            Generator::Id {
+                format,
+                namespace,
+                name,
+            } => new_id_at(
+                format,
+                cli.length,
+                namespace.as_deref(),
+                name.as_deref(),
+                at,
+            ),
+            Generator::Template(template) => template.render(index + 1, at),
+        }
+    }
 }
 
 pub fn parse_n_process() {
-    let cli = Cli::parse();
+    let loaded = config_files().and_then(|files| config::load(std::env::args_os(), &files));
+    let (cli, settings) = match loaded {
+        Ok(config) => (config.cli, config.settings),
+        Err(msg) => {
+            eprintln!("Error: {}", msg);
+            process::exit(exit_codes::USAGE_ERROR);
+        }
+    };
 
     // Handle subcommands first
     if let Some(command) = &cli.command {
         match command {
-            Commands::Inspect { id, json } => {
-                handle_inspect(id, *json);
+            Commands::Inspect {
+                id,
+                snowflake,
+                json,
+                output_format,
+                no_header,
+            } => {
+                let result = match snowflake {
+                    Some(preset) => inspect_snowflake(id, &snowflake_preset(*preset)),
+                    None => inspect_id(id),
+                };
+                let format = if *json {
+                    OutputFormat::Json
+                } else {
+                    output_format.unwrap_or(OutputFormat::Plain)
+                };
+                handle_inspect(id, result, format, *no_header);
+                return;
+            }
+            Commands::Encode {
+                numbers,
+                each,
+                sqids,
+                json,
+            } => {
+                handle_encode(numbers, *each, sqids, *json);
+                return;
+            }
+            Commands::Decode { ids, sqids, json } => {
+                handle_decode(ids, sqids, *json);
+                return;
+            }
+            Commands::Convert { id, format } => {
+                handle_convert(id, *format);
+                return;
+            }
+            Commands::Namespaces { json } => {
+                handle_namespaces(&cli, *json);
+                return;
+            }
+            Commands::Config {
+                action: ConfigAction::Show { json },
+            } => {
+                handle_config_show(&settings, *json);
                 return;
             }
             Commands::Completions { shell } => {
@@ -61,18 +213,132 @@ pub fn parse_n_process() {
         process::exit(exit_codes::USAGE_ERROR);
     }
 
-    // Convert CLI options to internal types
-    let (id_format, namespace, name) = match build_id_format(&cli) {
-        Ok(result) => result,
+    if cli.threads < 1 {
+        eprintln!("Error: --threads must be at least 1, got {}", cli.threads);
+        process::exit(exit_codes::USAGE_ERROR);
+    }
+
+    if cli.split_every == Some(0) {
+        eprintln!("Error: --split-every must be at least 1");
+        process::exit(exit_codes::USAGE_ERROR);
+    }
+
+    if cli.append && output_format(&cli, cli.json) == OutputFormat::Json {
+        eprintln!("Error: --append cannot add to a JSON array; use --output-format ndjson");
+        process::exit(exit_codes::USAGE_ERROR);
+    }
+
+    if !cli.columns.is_empty() && output_format(&cli, cli.json) == OutputFormat::Plain {
+        eprintln!("Error: --columns needs --output-format json, ndjson, csv or tsv");
+        process::exit(exit_codes::USAGE_ERROR);
+    }
+
+    // Convert CLI options to internal types. A template builds its own formats.
+    let built = match &cli.template {
+        Some(template) => build_template(&cli, template).map(Generator::Template),
+        None => build_id_format(&cli).map(|(format, namespace, name)| Generator::Id {
+            format,
+            namespace,
+            name,
+        }),
+    };
+    let generator = match built {
+        Ok(generator) => generator,
         Err(msg) => {
             eprintln!("Error: {}", msg);
             process::exit(exit_codes::USAGE_ERROR);
         }
     };
 
+    if cli.threads > 1 {
+        let conflict = if cli.seed.is_some() {
+            Some("--seed: seeded IDs depend on the order they are generated in")
+        } else if cli.monotonic {
+            Some("--monotonic: each ULID must be generated after the previous one")
+        } else if cli.from.is_some() && cli.spread == Spread::Random && cli.sorted {
+            Some("--spread random --sorted: each time is drawn after the previous one")
+        } else {
+            None
+        };
+        if let Some(conflict) = conflict {
+            eprintln!("Error: --threads cannot be combined with {}", conflict);
+            process::exit(exit_codes::USAGE_ERROR);
+        }
+    }
+
+    // A monotonic ULID never goes back in time, so unsorted random times would
+    // all collapse onto the latest one drawn so far
+    if cli.monotonic && cli.from.is_some() && cli.spread == Spread::Random && !cli.sorted {
+        eprintln!("Error: --monotonic with --spread random needs --sorted");
+        process::exit(exit_codes::USAGE_ERROR);
+    }
+
+    if let Some(seed) = cli.seed {
+        eprintln!("Warning: --seed makes IDs predictable; do not use seeded output where IDs must be secret or unguessable");
+        rng::set_seed(seed);
+    }
+
+    let spread = match (cli.from, cli.to) {
+        (Some(from), Some(to)) => {
+            match TimeSpread::new(from, to, cli.count, cli.spread, cli.sorted) {
+                Ok(spread) => Some(spread),
+                Err(msg) => {
+                    eprintln!("Error: {}", msg);
+                    process::exit(exit_codes::USAGE_ERROR);
+                }
+            }
+        }
+        _ => None,
+    };
+
+    if cli.stream {
+        if let Err(msg) = stream::watch_for_stop() {
+            eprintln!("Error: {}", msg);
+            process::exit(exit_codes::ERROR);
+        }
+    }
+
+    if let Some(path) = &cli.state_file {
+        if let Err(msg) = load_ulid_state(path) {
+            eprintln!("Error: {}", msg);
+            process::exit(exit_codes::ERROR);
+        }
+    }
+
+    if let (
+        Some(source),
+        Generator::Id {
+            format, namespace, ..
+        },
+    ) = (&cli.names_from, &generator)
+    {
+        let result = generate_from_names(format, &cli, namespace.as_deref(), source);
+        if let Err(err) = result.or_else(ignore_broken_pipe) {
+            eprintln!("Error: {}", err);
+            let exit_code = if err.is::<IDError>() {
+                exit_codes::USAGE_ERROR
+            } else {
+                exit_codes::ERROR
+            };
+            process::exit(exit_code);
+        }
+        return;
+    }
+
     // Generate IDs
-    match generate_ids(&id_format, &cli, namespace.as_deref(), name.as_deref()) {
-        Ok(_) => {}
+    let started = Instant::now();
+    match generate_ids(&generator, &cli, spread.as_ref()) {
+        Ok(generated) => {
+            if cli.stats {
+                print_stats(generated, started.elapsed(), cli.threads);
+            }
+            if let Some(path) = &cli.state_file {
+                if let Err(msg) = save_ulid_state(path) {
+                    eprintln!("Error: {}", msg);
+                    process::exit(exit_codes::ERROR);
+                }
+            }
+        }
         Err(err) => {
             eprintln!("Error: {}", err);
             let exit_code = if err.is::<IDError>() {
@@ -85,12 +351,33 @@ pub fn parse_n_process() {
     }
 }
 
-fn handle_inspect(id: &str, json_output: bool) {
-    let result = inspect_id(id);
-
-    if json_output {
+fn handle_inspect(id: &str, result: InspectionResult, format: OutputFormat, no_header: bool) {
+    if format == OutputFormat::Json {
         let json = serde_json::to_string_pretty(&result).unwrap();
         println!("{}", json);
+    } else if format == OutputFormat::Ndjson {
+        println!("{}", serde_json::to_string(&result).unwrap());
+    } else if matches!(format, OutputFormat::Csv | OutputFormat::Tsv) {
+        let mut out = io::stdout().lock();
+        let header = ["id", "valid", "type", "version", "variant", "timestamp"];
+        let valid = result.valid.to_string();
+        let row = [
+            id,
+            &valid,
+            &result.id_type,
+            result.version.as_deref().unwrap_or(""),
+            result.variant.as_deref().unwrap_or(""),
+            result.timestamp.as_deref().unwrap_or(""),
+        ];
+        let written = if no_header {
+            Ok(())
+        } else {
+            write_row(&mut out, format, &header)
+        };
+        if let Err(err) = written.and_then(|_| write_row(&mut out, format, &row)) {
+            eprintln!("Error: {}", err);
+            process::exit(exit_codes::ERROR);
+        }
     } else {
         println!("ID: {}", id);
         println!("Valid: {}", result.valid);
@@ -104,6 +391,9 @@ fn handle_inspect(id: &str, json_output: bool) {
         if let Some(ts) = &result.timestamp {
             println!("Timestamp: {}", ts);
         }
+        for (key, value) in &result.components {
+            println!("{}: {}", key, value);
+        }
     }
 
     if !result.valid {
@@ -111,25 +401,215 @@ fn handle_inspect(id: &str, json_output: bool) {
     }
 }
 
+fn build_sqids(args: &SqidsArgs) -> Result<Sqids, String> {
+    let mut blocklist = if args.no_default_blocklist {
+        HashSet::new()
+    } else {
+        sqids::default_blocklist()
+    };
+    blocklist.extend(args.blocklist.iter().cloned());
+
+    let mut builder = Sqids::builder()
+        .min_length(args.min_length)
+        .blocklist(blocklist);
+    if let Some(alphabet) = &args.alphabet {
+        builder = builder.alphabet(alphabet.chars().collect());
+    }
+    builder.build().map_err(|err| err.to_string())
+}
+
+fn handle_encode(numbers: &[u64], each: bool, args: &SqidsArgs, json_output: bool) {
+    let sqids = build_sqids(args).unwrap_or_else(|msg| {
+        eprintln!("Error: {}", msg);
+        process::exit(exit_codes::USAGE_ERROR);
+    });
+
+    let groups: Vec<Vec<u64>> = if each {
+        numbers.iter().map(|n| vec![*n]).collect()
+    } else {
+        vec![numbers.to_vec()]
+    };
+
+    let mut outputs = Vec::new();
+    for numbers in groups {
+        match sqids.encode(&numbers) {
+            Ok(value) => outputs.push(SqidsOutput { value, numbers }),
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                process::exit(exit_codes::ERROR);
+            }
+        }
+    }
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&outputs).unwrap());
+    } else {
+        for output in outputs {
+            println!("{}", output.value);
+        }
+    }
+}
+
+fn handle_decode(ids: &[String], args: &SqidsArgs, json_output: bool) {
+    let sqids = build_sqids(args).unwrap_or_else(|msg| {
+        eprintln!("Error: {}", msg);
+        process::exit(exit_codes::USAGE_ERROR);
+    });
+
+    let mut outputs = Vec::new();
+    for id in ids {
+        let numbers = sqids.decode(id);
+        // Several strings can decode to the same numbers; only the canonical one is valid
+        if numbers.is_empty() || sqids.encode(&numbers).ok().as_deref() != Some(id.as_str()) {
+            eprintln!("Error: '{}' is not a valid Sqids ID for these settings", id);
+            process::exit(exit_codes::ERROR);
+        }
+        outputs.push(SqidsOutput {
+            value: id.clone(),
+            numbers,
+        });
+    }
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&outputs).unwrap());
+    } else {
+        for output in outputs {
+            let numbers: Vec<String> = output.numbers.iter().map(|n| n.to_string()).collect();
+            println!("{}", numbers.join(" "));
+        }
+    }
+}
+
+fn handle_convert(id: &str, format: UuidFormat) {
+    let converted = Uuid::parse_str(id)
+        .map_err(|e| IDError::InvalidUuid(format!("Invalid UUID '{}': {}", id, e)))
+        .and_then(|uuid| match uuid.get_version_num() {
+            1 => uuid_v1_to_v6(&uuid),
+            6 => uuid_v6_to_v1(&uuid),
+            n => Err(IDError::InvalidUuid(format!(
+                "Only UUID v1 and v6 can be converted, got version {}",
+                n
+            ))),
+        });
+
+    match converted {
+        Ok(uuid) => match format {
+            UuidFormat::Simple => println!("{}", uuid.simple()),
+            UuidFormat::Hyphenated => println!("{}", uuid.hyphenated()),
+            UuidFormat::Urn => println!("{}", uuid.urn()),
+        },
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(exit_codes::USAGE_ERROR);
+        }
+    }
+}
+
+/// Built-in namespaces plus those from the config files and --define-namespace.
+/// The project config's namespaces replace the user config's of the same name.
+fn build_namespace_registry(cli: &Cli) -> Result<NamespaceRegistry, String> {
+    let mut registry = NamespaceRegistry::new();
+    for path in config_paths() {
+        registry.load_config(&path).map_err(|err| err.to_string())?;
+    }
+    for definition in &cli.define_namespace {
+        registry
+            .define_from_arg(definition)
+            .map_err(|err| err.to_string())?;
+    }
+    Ok(registry)
+}
+
+fn handle_namespaces(cli: &Cli, json_output: bool) {
+    let registry = match build_namespace_registry(cli) {
+        Ok(registry) => registry,
+        Err(msg) => {
+            eprintln!("Error: {}", msg);
+            process::exit(exit_codes::USAGE_ERROR);
+        }
+    };
+    let entries = registry.entries();
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&entries).unwrap());
+    } else {
+        let width = entries
+            .iter()
+            .map(|entry| entry.name.len())
+            .max()
+            .unwrap_or(0);
+        for entry in &entries {
+            println!(
+                "{:<width$}  {}  {}",
+                entry.name,
+                entry.uuid,
+                entry.source,
+                width = width
+            );
+        }
+    }
+}
+
+fn handle_config_show(settings: &[Setting], json_output: bool) {
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(settings).unwrap());
+        return;
+    }
+
+    let value = |setting: &Setting| {
+        if setting.value.is_empty() {
+            "\"\"".to_string()
+        } else {
+            setting.value.clone()
+        }
+    };
+    let name_width = settings.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    let value_width = settings.iter().map(|s| value(s).len()).max().unwrap_or(0);
+    for setting in settings {
+        println!(
+            "{:<name_width$}  {:<value_width$}  {}",
+            setting.name,
+            value(setting),
+            setting.source,
+            name_width = name_width,
+            value_width = value_width
+        );
+    }
+}
+
 fn build_id_format(cli: &Cli) -> Result<(IDFormat, Option<String>, Option<String>), String> {
     let uuid_version = match cli.id_type {
-        IdType::Uuid1 => Some(UuidVersion::V1),
+        IdType::Uuid1 => Some(UuidVersion::V1(build_v1_fields(cli)?)),
         IdType::Uuid3 => Some(UuidVersion::V3),
         IdType::Uuid4 => Some(UuidVersion::V4),
         IdType::Uuid5 => Some(UuidVersion::V5),
+        IdType::Uuid6 => Some(UuidVersion::V6(build_v1_fields(cli)?)),
+        IdType::Uuid7 => Some(UuidVersion::V7),
+        IdType::Uuid8 => Some(UuidVersion::V8(build_v8_fields(cli)?)),
         _ => None,
     };
 
     // Handle namespace resolution for v3/v5
     let namespace = if matches!(cli.id_type, IdType::Uuid3 | IdType::Uuid5) {
         match &cli.namespace {
-            Some(ns) => match resolve_namespace(ns) {
-                Ok(resolved) => Some(resolved),
-                Err(e) => return Err(e),
-            },
+            Some(ns) => {
+                let derive = if cli.id_type == IdType::Uuid3 {
+                    Uuid::new_v3
+                } else {
+                    Uuid::new_v5
+                };
+                let steps = resolve_namespace_path(ns, &build_namespace_registry(cli)?, derive)
+                    .map_err(|err| err.to_string())?;
+                if cli.verbose {
+                    for (path, uuid) in &steps {
+                        eprintln!("namespace {} = {}", path, uuid);
+                    }
+                }
+                steps.last().map(|(_, uuid)| uuid.to_string())
+            }
             None => {
                 return Err(format!(
-                    "UUID {} requires --namespace parameter. Use DNS, URL, OID, X500, or a custom UUID.",
+                    "UUID {} requires --namespace parameter. Use DNS, URL, OID, X500, a custom namespace name, or a UUID.",
                     if cli.id_type == IdType::Uuid3 { "v3" } else { "v5" }
                 ));
             }
@@ -138,7 +618,11 @@ fn build_id_format(cli: &Cli) -> Result<(IDFormat, Option<String>, Option<String
         None
     };
 
-    let name = if matches!(cli.id_type, IdType::Uuid3 | IdType::Uuid5) {
+    if cli.names_from.is_some() && !matches!(cli.id_type, IdType::Uuid3 | IdType::Uuid5) {
+        return Err("--names-from is only supported for uuid3 and uuid5".to_string());
+    }
+
+    let name = if matches!(cli.id_type, IdType::Uuid3 | IdType::Uuid5) && cli.names_from.is_none() {
         match &cli.name {
             Some(n) => Some(n.clone()),
             None => {
@@ -157,7 +641,13 @@ fn build_id_format(cli: &Cli) -> Result<(IDFormat, Option<String>, Option<String
     };
 
     let format = match cli.id_type {
-        IdType::Uuid1 | IdType::Uuid3 | IdType::Uuid4 | IdType::Uuid5 => {
+        IdType::Uuid1
+        | IdType::Uuid3
+        | IdType::Uuid4
+        | IdType::Uuid5
+        | IdType::Uuid6
+        | IdType::Uuid7
+        | IdType::Uuid8 => {
             let version = uuid_version.unwrap();
             match cli.format {
                 UuidFormat::Simple => IDFormat::Simple(version),
@@ -165,48 +655,700 @@ fn build_id_format(cli: &Cli) -> Result<(IDFormat, Option<String>, Option<String
                 UuidFormat::Urn => IDFormat::URN(version),
             }
         }
-        IdType::NanoId => IDFormat::NanoID,
+        IdType::NanoId => IDFormat::NanoID(build_nano_alphabet(cli)?),
         IdType::Cuid1 => IDFormat::Cuid(CuidVersion::V1),
         IdType::Cuid2 => IDFormat::Cuid(CuidVersion::V2),
+        IdType::Ulid if cli.monotonic => IDFormat::MonotonicUlid,
         IdType::Ulid => IDFormat::Ulid,
         IdType::ObjectId => IDFormat::OID,
+        IdType::Snowflake => IDFormat::Snowflake(build_snowflake_config(cli)?),
+        IdType::Ksuid => IDFormat::Ksuid,
+        IdType::TypeId => {
+            validate_prefix(&cli.type_prefix).map_err(|err| err.to_string())?;
+            IDFormat::TypeId(cli.type_prefix.clone())
+        }
+        IdType::Xid => IDFormat::Xid,
+    };
+
+    if cli.monotonic && cli.id_type != IdType::Ulid {
+        return Err("--monotonic is only supported for ulid".to_string());
+    }
+
+    let time_option = if cli.at.is_some() {
+        Some("--at")
+    } else if cli.from.is_some() {
+        Some("--from/--to")
+    } else {
+        None
     };
+    if let Some(option) = time_option {
+        if !format.has_timestamp() {
+            return Err(format!(
+                "{} is not supported for {}: it does not embed a timestamp",
+                option,
+                cli.id_type.to_possible_value().unwrap().get_name()
+            ));
+        }
+    }
+
+    check_seed(cli, cli.id_type, &format)?;
 
     Ok((format, namespace, name))
 }
 
+/// Rejects ID types that --seed cannot make reproducible
+fn check_seed(cli: &Cli, id_type: IdType, format: &IDFormat) -> Result<(), String> {
+    if cli.seed.is_none() {
+        return Ok(());
+    }
+    if id_type == IdType::Cuid1 {
+        return Err(
+            "--seed is not supported for cuid1: its fingerprint comes from the host".to_string(),
+        );
+    }
+    if format.has_timestamp() && cli.at.is_none() && cli.from.is_none() {
+        return Err(format!(
+            "--seed needs --at or --from/--to for {}, since it embeds the current time",
+            id_type.to_possible_value().unwrap().get_name()
+        ));
+    }
+    Ok(())
+}
+
+/**
+ * Parses --template, building each ID placeholder with `build_id_format` as if
+ * its type had been given with -t
+ *
+ * Placeholder options override the matching command-line option: the UUID
+ * format for UUIDs, `LENGTH`, `ALPHABET` or `LENGTH:ALPHABET` for NanoIDs, the
+ * type prefix for TypeIDs and the preset for Snowflakes.
+ */
+fn build_template(cli: &Cli, template: &str) -> Result<Template, String> {
+    let parsed = Template::parse(template, |name, options| {
+        let id_type = IdType::from_str(name, true).map_err(|_| {
+            IDError::InvalidTemplate(format!(
+                "Unknown placeholder '{{{}}}'; use an ID type such as ulid or nanoid, seq or date",
+                name
+            ))
+        })?;
+        let invalid = |err: String| {
+            IDError::InvalidTemplate(format!("Invalid placeholder '{{{}}}': {}", name, err))
+        };
+
+        // Time options are applied when rendering, and --seed is checked below
+        let mut placeholder_cli = cli.clone();
+        placeholder_cli.id_type = id_type;
+        placeholder_cli.monotonic = cli.monotonic && id_type == IdType::Ulid;
+        placeholder_cli.at = None;
+        placeholder_cli.from = None;
+        placeholder_cli.to = None;
+        placeholder_cli.seed = None;
+
+        if let Some(options) = options {
+            match id_type {
+                IdType::Uuid1
+                | IdType::Uuid3
+                | IdType::Uuid4
+                | IdType::Uuid5
+                | IdType::Uuid6
+                | IdType::Uuid7
+                | IdType::Uuid8 => {
+                    placeholder_cli.format = UuidFormat::from_str(options, true)
+                        .map_err(|_| invalid(format!("unknown UUID format '{}'", options)))?;
+                }
+                IdType::NanoId => {
+                    let (length, alphabet) = parse_nanoid_options(options).map_err(invalid)?;
+                    placeholder_cli.length = length.or(placeholder_cli.length);
+                    placeholder_cli.alphabet = alphabet.or(placeholder_cli.alphabet);
+                }
+                IdType::TypeId => placeholder_cli.type_prefix = options.to_string(),
+                IdType::Snowflake => {
+                    placeholder_cli.preset = SnowflakePreset::from_str(options, true)
+                        .map_err(|_| invalid(format!("unknown Snowflake preset '{}'", options)))?;
+                }
+                _ => return Err(invalid("this ID type takes no options".to_string())),
+            }
+        }
+
+        let (format, namespace, name) = build_id_format(&placeholder_cli).map_err(invalid)?;
+        check_seed(cli, id_type, &format).map_err(invalid)?;
+        Ok(IdPlaceholder {
+            format,
+            len: placeholder_cli.length,
+            namespace,
+            name,
+        })
+    })
+    .map_err(|err| err.to_string())?;
+
+    if cli.seed.is_some() && cli.at.is_none() && cli.from.is_none() && parsed.uses_time() {
+        return Err(
+            "--seed needs --at or --from/--to for a template that includes the current time"
+                .to_string(),
+        );
+    }
+    Ok(parsed)
+}
+
+/**
+ * Splits NanoID placeholder options, written `LENGTH`, `ALPHABET` or
+ * `LENGTH:ALPHABET`
+ *
+ * A lone option is a length when it is a number without a leading zero, so
+ * digit alphabets such as `01` or `0123456789` still work on their own.
+ *
+ * # Arguments
+ *
+ * * `options` - The text after `nanoid:`
+ */
+fn parse_nanoid_options(options: &str) -> Result<(Option<usize>, Option<String>), String> {
+    let segments: Vec<&str> = options.split(':').collect();
+    match segments[..] {
+        [option] if !option.starts_with('0') => match option.parse() {
+            Ok(length) => Ok((Some(length), None)),
+            Err(_) => Ok((None, Some(option.to_string()))),
+        },
+        [alphabet] => Ok((None, Some(alphabet.to_string()))),
+        [length, alphabet] => {
+            let length = length.parse().map_err(|_| {
+                format!("expected LENGTH:ALPHABET, but '{}' is not a length", length)
+            })?;
+            Ok((Some(length), Some(alphabet.to_string())))
+        }
+        _ => Err(format!(
+            "expected LENGTH, ALPHABET or LENGTH:ALPHABET, got '{}'",
+            options
+        )),
+    }
+}
+
+fn build_nano_alphabet(cli: &Cli) -> Result<Option<NanoAlphabet>, String> {
+    match &cli.alphabet {
+        Some(alphabet) => NanoAlphabet::preset(alphabet)
+            .map(Ok)
+            .unwrap_or_else(|| NanoAlphabet::new(alphabet))
+            .map(Some)
+            .map_err(|err| err.to_string()),
+        None => Ok(None),
+    }
+}
+
+fn build_v1_fields(cli: &Cli) -> Result<UuidV1Fields, String> {
+    let node = match cli.node.as_deref() {
+        None | Some("random") => None,
+        Some("machine") => Some(UuidV1Fields::machine_node().map_err(|err| err.to_string())?),
+        Some(node) => Some(UuidV1Fields::parse_node(node).map_err(|err| err.to_string())?),
+    };
+    UuidV1Fields::new(node, cli.clock_seq).map_err(|err| err.to_string())
+}
+
+fn build_v8_fields(cli: &Cli) -> Result<UuidV8Fields, String> {
+    match &cli.payload {
+        Some(payload) => UuidV8Fields::from_hex(payload),
+        None => UuidV8Fields::new(cli.custom_a, cli.custom_b, cli.custom_c),
+    }
+    .map_err(|err| err.to_string())
+}
+
+fn snowflake_preset(preset: SnowflakePreset) -> SnowflakeConfig {
+    match preset {
+        SnowflakePreset::Twitter => SnowflakeConfig::twitter(),
+        SnowflakePreset::Discord => SnowflakeConfig::discord(),
+        SnowflakePreset::Instagram => SnowflakeConfig::instagram(),
+    }
+}
+
+fn build_snowflake_config(cli: &Cli) -> Result<SnowflakeConfig, String> {
+    let preset = snowflake_preset(cli.preset);
+    let config = SnowflakeConfig {
+        epoch: cli.epoch.unwrap_or(preset.epoch),
+        reserved_bits: preset.reserved_bits,
+        datacenter_bits: cli.datacenter_bits.unwrap_or(preset.datacenter_bits),
+        worker_bits: cli.worker_bits.unwrap_or(preset.worker_bits),
+        sequence_bits: cli.sequence_bits.unwrap_or(preset.sequence_bits),
+        datacenter_id: cli.datacenter_id,
+        worker_id: cli.worker_id,
+    };
+    config.validate().map_err(|err| err.to_string())?;
+    Ok(config)
+}
+
+/// Continues the monotonic ULID sequence from a state file, if it exists yet
+fn load_ulid_state(path: &Path) -> Result<(), String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => {
+            return Err(format!(
+                "Cannot read state file '{}': {}",
+                path.display(),
+                err
+            ))
+        }
+    };
+    let last = Ulid::from_string(contents.trim()).map_err(|err| {
+        format!(
+            "State file '{}' does not hold a valid ULID: {}",
+            path.display(),
+            err
+        )
+    })?;
+    resume_monotonic_ulid(last);
+    Ok(())
+}
+
+/// Records the last issued monotonic ULID in the state file, replacing it
+/// atomically so an interrupted run never leaves it half written
+fn save_ulid_state(path: &Path) -> Result<(), String> {
+    match last_monotonic_ulid() {
+        Some(last) => AtomicFile::create(path)
+            .and_then(|mut file| {
+                writeln!(file, "{}", last)?;
+                file.commit()
+            })
+            .map_err(|err| format!("Cannot write state file '{}': {}", path.display(), err)),
+        None => Ok(()),
+    }
+}
+
+/// `count` timestamps between --from and --to (inclusive). Even steps are
+/// computed per ID and random times are drawn per ID, so nothing is stored.
+/// Sorted random times are drawn in order, each as the earliest of the times
+/// still to come.
+struct TimeSpread {
+    from: SystemTime,
+    /// Nanoseconds from `from` to `to`
+    range: u128,
+    count: u64,
+    spread: Spread,
+    /// For --spread random --sorted, the fraction of the range after the last
+    /// time drawn
+    sorted_gap: Option<Mutex<f64>>,
+}
+
+impl TimeSpread {
+    fn new(
+        from: SystemTime,
+        to: SystemTime,
+        count: u64,
+        spread: Spread,
+        sorted: bool,
+    ) -> Result<Self, String> {
+        let range = to
+            .duration_since(from)
+            .map_err(|_| "--from must not be later than --to".to_string())?
+            .as_nanos();
+
+        Ok(TimeSpread {
+            from,
+            range,
+            count,
+            spread,
+            sorted_gap: (spread == Spread::Random && sorted).then(|| Mutex::new(1.0)),
+        })
+    }
+
+    /// The timestamp of the ID at position `index` (from 0). With --spread
+    /// random --sorted, IDs must be asked for in order.
+    fn at(&self, index: u64) -> SystemTime {
+        let offset = match (self.spread, &self.sorted_gap) {
+            (Spread::Random, None) => rng::random_range(0..=self.range),
+            (Spread::Random, Some(gap)) => {
+                // The smallest of n uniform values leaves a gap distributed as
+                // V^(1/n) of the space above it, for V uniform in (0, 1]
+                let mut gap = gap.lock().unwrap_or_else(|e| e.into_inner());
+                let left = (self.count - index) as f64;
+                *gap *= (1.0 - rng::random::<f64>()).powf(1.0 / left);
+                self.range - ((self.range as f64 * *gap) as u128).min(self.range)
+            }
+            (Spread::Even, _) if self.count == 1 => 0,
+            (Spread::Even, _) => self.range * index as u128 / (self.count as u128 - 1),
+        };
+        self.from
+            + Duration::new(
+                (offset / 1_000_000_000) as u64,
+                (offset % 1_000_000_000) as u32,
+            )
+    }
+}
+
 fn generate_ids(
+    generator: &Generator,
+    cli: &Cli,
+    spread: Option<&TimeSpread>,
+) -> Result<u64, BoxError> {
+    let mut writer = open_output(cli, &id_header(cli), 0)?;
+
+    let mut generated = 0;
+    let result = if cli.threads > 1 {
+        generate_parallel(&mut writer, generator, cli, spread, &mut generated)
+    } else if cli.stream {
+        generate_stream(&mut writer, generator, cli, &mut generated)
+    } else {
+        (0..cli.count).try_for_each(|i| {
+            next_part(&mut writer, cli, i)?;
+            write_id(&mut writer, generator, cli, spread, i)?;
+            generated += 1;
+            Ok(())
+        })
+    };
+
+    result
+        .and_then(|_| Ok(writer.finish()?.commit()?))
+        .or_else(ignore_broken_pipe)?;
+    Ok(generated)
+}
+
+/// The CSV and TSV header for generated IDs
+fn id_header(cli: &Cli) -> Vec<&'static str> {
+    let mut header = vec!["value"];
+    header.extend(cli.columns.iter().map(|column| match column {
+        Column::Type => "type",
+        Column::Timestamp => "timestamp",
+    }));
+    header
+}
+
+/// Opens stdout or the --output file, or with --split-every the file that
+/// holds the ID at position `index` (from 0)
+fn open_output(cli: &Cli, header: &[&str], index: u64) -> Result<RecordWriter<Output>, BoxError> {
+    let out = match &cli.output {
+        Some(path) => {
+            let path = match cli.split_every {
+                Some(split) => split_path(path, index / split + 1),
+                None => path.clone(),
+            };
+            Output::file(&path, cli.append)
+                .map_err(|err| format!("Cannot write '{}': {}", path.display(), err))?
+        }
+        None => Output::stdout(),
+    };
+    // An appended file already has its header
+    let header = (!cli.no_header && !out.has_content()?).then_some(header);
+    Ok(RecordWriter::new(
+        out,
+        output_format(cli, cli.json),
+        header,
+    )?)
+}
+
+/// With --split-every, completes the current file and moves on to the next
+/// when the ID at position `index` (from 0) starts a new one
+fn next_part(writer: &mut RecordWriter<Output>, cli: &Cli, index: u64) -> Result<(), BoxError> {
+    if let Some(split) = cli.split_every {
+        if index > 0 && index.is_multiple_of(split) {
+            let next = open_output(cli, &id_header(cli), index)?;
+            std::mem::replace(writer, next).finish()?.commit()?;
+        }
+    }
+    Ok(())
+}
+
+/// Treats a closed stdout as the end of the output rather than an error, so
+/// piping into a reader that quits early, like `head`, exits cleanly
+fn ignore_broken_pipe(err: BoxError) -> Result<(), BoxError> {
+    match err.downcast_ref::<io::Error>() {
+        Some(io_err) if io_err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        _ => Err(err),
+    }
+}
+
+/// Generates with --threads workers. Worker `w` formats chunks `w`, `w + threads`,
+/// and so on, and the chunks are written back in turn, so the output is in the
+/// same order as with a single thread and at most a few chunks are held at once.
+fn generate_parallel(
+    writer: &mut RecordWriter<Output>,
+    generator: &Generator,
+    cli: &Cli,
+    spread: Option<&TimeSpread>,
+    generated: &mut u64,
+) -> Result<(), BoxError> {
+    let chunks = chunk_count(cli);
+    let format = writer.format;
+
+    thread::scope(|scope| {
+        let receivers: Vec<_> = (0..cli.threads as u64)
+            .map(|worker| {
+                let (sender, receiver) = mpsc::sync_channel::<Result<Vec<u8>, BoxError>>(2);
+                scope.spawn(move || {
+                    for chunk in (worker..chunks).step_by(cli.threads) {
+                        let mut range = chunk_range(cli, chunk);
+                        // Each --split-every file is a separate output
+                        let part = cli.split_every.unwrap_or(cli.count);
+                        let mut out = RecordWriter::continuing(
+                            Vec::new(),
+                            format,
+                            range.start.is_multiple_of(part),
+                        );
+                        let result = range
+                            .try_for_each(|i| write_id(&mut out, generator, cli, spread, i))
+                            .map(|_| out.into_inner());
+                        let failed = result.is_err();
+                        // Stop once the output has failed or this chunk did
+                        if sender.send(result).is_err() || failed {
+                            break;
+                        }
+                    }
+                });
+                receiver
+            })
+            .collect();
+
+        for chunk in 0..chunks {
+            let bytes = receivers[(chunk % cli.threads as u64) as usize].recv()??;
+            let range = chunk_range(cli, chunk);
+            next_part(writer, cli, range.start)?;
+            writer.write_raw(&bytes)?;
+            *generated = range.end;
+        }
+        Ok(())
+    })
+}
+
+/// The number of --threads chunks: runs of up to `CHUNK_SIZE` IDs that never
+/// cross a --split-every boundary, so each file starts with a new chunk
+fn chunk_count(cli: &Cli) -> u64 {
+    let part = cli.split_every.unwrap_or(cli.count);
+    let full_parts = (cli.count - 1) / part;
+    full_parts * part.div_ceil(CHUNK_SIZE) + (cli.count - full_parts * part).div_ceil(CHUNK_SIZE)
+}
+
+/// The positions of the IDs in chunk `chunk` (from 0)
+fn chunk_range(cli: &Cli, chunk: u64) -> Range<u64> {
+    let part = cli.split_every.unwrap_or(cli.count);
+    let chunks_per_part = part.div_ceil(CHUNK_SIZE);
+    let part_start = chunk / chunks_per_part * part;
+    let start = part_start + chunk % chunks_per_part * CHUNK_SIZE;
+    let end = (start + CHUNK_SIZE).min(part_start + part).min(cli.count);
+    start..end
+}
+
+/// Generates IDs for --stream until it is stopped or its --duration passes
+fn generate_stream(
+    writer: &mut RecordWriter<Output>,
+    generator: &Generator,
+    cli: &Cli,
+    generated: &mut u64,
+) -> Result<(), BoxError> {
+    let stream = Stream::new(cli.duration, cli.rate);
+    while stream.next(*generated) {
+        next_part(writer, cli, *generated)?;
+        write_id(writer, generator, cli, None, *generated)?;
+        *generated += 1;
+        // Paced IDs are passed on as soon as they are due
+        if cli.rate.is_some() {
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the ID at position `index` (from 0) along with its --columns
+fn write_id<W: Write>(
+    writer: &mut RecordWriter<W>,
+    generator: &Generator,
+    cli: &Cli,
+    spread: Option<&TimeSpread>,
+    index: u64,
+) -> Result<(), BoxError> {
+    // Each ID gets its own timestamp from the spread, otherwise --at (or now)
+    let at = spread.map_or(cli.at, |spread| Some(spread.at(index)));
+    let id = generator.generate(cli, index, at)?;
+    let record = IdOutput {
+        value: format!("{}{}{}", cli.prefix, id, cli.suffix),
+        id_type: cli
+            .columns
+            .contains(&Column::Type)
+            .then(|| generator.type_name(cli)),
+        timestamp: cli
+            .columns
+            .contains(&Column::Timestamp)
+            .then(|| generator.timestamp(&id)),
+    };
+
+    let mut fields = vec![record.value.as_str()];
+    fields.extend(cli.columns.iter().map(|column| {
+        match column {
+            Column::Type => record.id_type.as_deref().unwrap_or(""),
+            Column::Timestamp => record
+                .timestamp
+                .as_ref()
+                .and_then(|t| t.as_deref())
+                .unwrap_or(""),
+        }
+    }));
+    writer.write(&record, &fields)
+}
+
+/// Prints the --stats summary to stderr
+fn print_stats(count: u64, elapsed: Duration, threads: usize) {
+    let seconds = elapsed.as_secs_f64();
+    eprintln!(
+        "Generated {} IDs in {:.3}s ({:.0} IDs/s, {} thread{})",
+        count,
+        seconds,
+        count as f64 / seconds.max(f64::EPSILON),
+        threads,
+        if threads == 1 { "" } else { "s" }
+    );
+}
+
+/// Writes a UUID v3/v5 for every line of `source` (a path, or `-` for stdin).
+/// Lines are streamed one at a time, so input size is not limited by memory.
+fn generate_from_names(
     id_format: &IDFormat,
     cli: &Cli,
     namespace: Option<&str>,
-    name: Option<&str>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let len = cli.length;
-
-    if cli.json {
-        let mut ids = Vec::new();
-        for _ in 0..cli.count {
-            let id = new_id(id_format, len, namespace, name)?;
-            ids.push(IdOutput {
-                value: format!("{}{}{}", cli.prefix, id, cli.suffix),
-            });
-        }
-        let json = serde_json::to_string_pretty(&ids)?;
-        println!("{}", json);
+    source: &str,
+) -> Result<(), BoxError> {
+    let reader: Box<dyn BufRead> = if source == "-" {
+        Box::new(io::stdin().lock())
     } else {
-        for i in 0..cli.count {
-            let id = new_id(id_format, len, namespace, name)?;
-            print!("{}{}{}", cli.prefix, id, cli.suffix);
-            if i < cli.count - 1 {
-                println!();
-            }
+        let file = File::open(source)
+            .map_err(|err| format!("Cannot open names file '{}': {}", source, err))?;
+        Box::new(BufReader::new(file))
+    };
+    let mut writer = open_output(cli, &["name", "uuid"], 0)?;
+
+    for line in reader.lines() {
+        let line = line?;
+        let name = line.strip_suffix('\r').unwrap_or(&line);
+        if name.is_empty() {
+            continue;
         }
-        println!();
+
+        let id = new_id(id_format, None, namespace, Some(name))?;
+        let uuid = format!("{}{}{}", cli.prefix, id, cli.suffix);
+        let fields = [name, &uuid];
+        writer.write(
+            &NameOutput {
+                name,
+                uuid: uuid.clone(),
+            },
+            &fields,
+        )?;
     }
 
+    writer.finish()?.commit()?;
     Ok(())
 }
 
+/// The --output-format in effect, where `json` is a --json flag
+fn output_format(cli: &Cli, json: bool) -> OutputFormat {
+    if json {
+        OutputFormat::Json
+    } else if cli.csv {
+        OutputFormat::Csv
+    } else {
+        cli.output_format.unwrap_or(OutputFormat::Plain)
+    }
+}
+
+/// Writes records one at a time in an --output-format. Plain output is the
+/// fields separated by tabs, without a header.
+struct RecordWriter<W: Write> {
+    out: W,
+    format: OutputFormat,
+    first: bool,
+}
+
+impl<W: Write> RecordWriter<W> {
+    /// Starts the output, with `header` as the first CSV or TSV row if given
+    fn new(out: W, format: OutputFormat, header: Option<&[&str]>) -> io::Result<Self> {
+        let mut writer = RecordWriter::continuing(out, format, true);
+        match (format, header) {
+            (OutputFormat::Json, _) => write!(writer.out, "[")?,
+            (OutputFormat::Csv | OutputFormat::Tsv, Some(header)) => {
+                write_row(&mut writer.out, format, header)?
+            }
+            _ => {}
+        }
+        Ok(writer)
+    }
+
+    /// A writer for records in the middle of an output, without its opening.
+    /// `first` is whether these are the first records of the output.
+    fn continuing(out: W, format: OutputFormat, first: bool) -> Self {
+        RecordWriter { out, format, first }
+    }
+
+    /// Writes `record` for JSON formats, or its `fields` for the others
+    fn write<T: Serialize, S: AsRef<str>>(
+        &mut self,
+        record: &T,
+        fields: &[S],
+    ) -> Result<(), BoxError> {
+        match self.format {
+            // Laid out like serde_json's pretty-printed arrays
+            OutputFormat::Json => {
+                let json = serde_json::to_string_pretty(record)?;
+                let separator = if self.first { "\n  " } else { ",\n  " };
+                write!(self.out, "{}{}", separator, json.replace('\n', "\n  "))?;
+            }
+            OutputFormat::Ndjson => writeln!(self.out, "{}", serde_json::to_string(record)?)?,
+            _ => write_row(&mut self.out, self.format, fields)?,
+        }
+        self.first = false;
+        Ok(())
+    }
+
+    /// Writes records already formatted by a `continuing` writer
+    fn write_raw(&mut self, records: &[u8]) -> io::Result<()> {
+        self.first &= records.is_empty();
+        self.out.write_all(records)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+
+    fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// Ends the output, such as closing a JSON array, and returns the writer
+    fn finish(mut self) -> io::Result<W> {
+        if self.format == OutputFormat::Json {
+            writeln!(self.out, "{}]", if self.first { "" } else { "\n" })?;
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+/// Writes one CSV row, or tab-separated fields for the other formats
+fn write_row<W: Write, S: AsRef<str>>(
+    out: &mut W,
+    format: OutputFormat,
+    fields: &[S],
+) -> io::Result<()> {
+    let separator = if format == OutputFormat::Csv {
+        ","
+    } else {
+        "\t"
+    };
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.write_all(separator.as_bytes())?;
+        }
+        if format == OutputFormat::Csv {
+            out.write_all(csv_field(field.as_ref()).as_bytes())?;
+        } else {
+            out.write_all(field.as_ref().as_bytes())?;
+        }
+    }
+    writeln!(out)
+}
+
+/// Quotes a CSV field if it contains a comma, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 fn print_banner() {
     let banner = r#" _     _
 (_) __| | __ _  ___ _ __
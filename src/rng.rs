@@ -0,0 +1,69 @@
+use rand::distr::uniform::{SampleRange, SampleUniform};
+use rand::distr::{Distribution, StandardUniform};
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::sync::{Mutex, OnceLock};
+
+/// Seeded generator shared by all generators in the process, once a seed is set.
+/// Unseeded runs never touch it, so they don't contend on the lock.
+static SEEDED: OnceLock<Mutex<ChaCha8Rng>> = OnceLock::new();
+
+/**
+ * Switches every generator to a deterministic PRNG
+ *
+ * After this, the same seed and the same sequence of calls produce the same
+ * IDs. Seeded output is predictable and must not be used where IDs need to be
+ * unguessable.
+ *
+ * # Arguments
+ *
+ * * `seed` - The PRNG seed
+ */
+pub fn set_seed(seed: u64) {
+    let rng = ChaCha8Rng::seed_from_u64(seed);
+    if let Err(rng) = SEEDED.set(Mutex::new(rng)) {
+        let rng = rng.into_inner().unwrap_or_else(|e| e.into_inner());
+        *SEEDED
+            .get()
+            .unwrap()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = rng;
+    }
+}
+
+/// Whether `set_seed` has been called
+pub fn is_seeded() -> bool {
+    SEEDED.get().is_some()
+}
+
+/// Runs `f` with the seeded PRNG, or with the thread-local RNG if no seed is set
+pub fn with_rng<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    match SEEDED.get() {
+        Some(seeded) => f(&mut *seeded.lock().unwrap_or_else(|e| e.into_inner())),
+        None => f(&mut rand::rng()),
+    }
+}
+
+/// A random value, like `rand::random`
+pub fn random<T>() -> T
+where
+    StandardUniform: Distribution<T>,
+{
+    with_rng(|rng| rng.random())
+}
+
+/// A random value in `range`, like `rand::random_range`
+pub fn random_range<T, R>(range: R) -> T
+where
+    T: SampleUniform,
+    R: SampleRange<T>,
+{
+    with_rng(|rng| rng.random_range(range))
+}
+
+/// `size` random bytes, in the form `nanoid::format` expects
+pub fn random_bytes(size: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; size];
+    with_rng(|rng| rng.fill_bytes(&mut bytes));
+    bytes
+}
@@ -0,0 +1,243 @@
+use crate::id::IDError;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Bit layout and identity of a Snowflake ID generator.
+///
+/// An ID is laid out (most significant bits first) as
+/// `reserved | timestamp | datacenter_id | worker_id | sequence`, where the
+/// reserved bits are always zero and the timestamp takes whatever bits the
+/// other fields leave free.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnowflakeConfig {
+    /// Custom epoch in milliseconds since the Unix epoch
+    pub epoch: u64,
+    /// High bits kept zero, such as the sign bit in the Twitter layout
+    pub reserved_bits: u8,
+    pub datacenter_bits: u8,
+    pub worker_bits: u8,
+    pub sequence_bits: u8,
+    pub datacenter_id: u64,
+    pub worker_id: u64,
+}
+
+/// Fields decoded from a Snowflake ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnowflakeParts {
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+    pub datacenter_id: u64,
+    pub worker_id: u64,
+    pub sequence: u64,
+}
+
+/// Last issued (Unix millisecond, sequence) pair, shared by all generators in the process
+static LAST_ISSUED: Mutex<(u64, u64)> = Mutex::new((0, 0));
+
+impl SnowflakeConfig {
+    /// Twitter layout: unused sign bit, 41-bit timestamp, 5-bit datacenter, 5-bit
+    /// worker, 12-bit sequence
+    pub fn twitter() -> Self {
+        SnowflakeConfig {
+            epoch: 1_288_834_974_657,
+            reserved_bits: 1,
+            datacenter_bits: 5,
+            worker_bits: 5,
+            sequence_bits: 12,
+            datacenter_id: 0,
+            worker_id: 0,
+        }
+    }
+
+    /// Discord layout: 42-bit timestamp, 5-bit worker, 5-bit process, 12-bit increment.
+    /// The internal worker maps to `datacenter_id` and the process to `worker_id`.
+    pub fn discord() -> Self {
+        SnowflakeConfig {
+            epoch: 1_420_070_400_000,
+            reserved_bits: 0,
+            datacenter_bits: 5,
+            worker_bits: 5,
+            sequence_bits: 12,
+            datacenter_id: 0,
+            worker_id: 0,
+        }
+    }
+
+    /// Instagram layout: 41-bit timestamp, 13-bit shard, 10-bit sequence.
+    /// The shard maps to `worker_id`.
+    pub fn instagram() -> Self {
+        SnowflakeConfig {
+            epoch: 1_314_220_021_721,
+            reserved_bits: 0,
+            datacenter_bits: 0,
+            worker_bits: 13,
+            sequence_bits: 10,
+            datacenter_id: 0,
+            worker_id: 0,
+        }
+    }
+
+    /// Number of bits left for the timestamp
+    pub fn timestamp_bits(&self) -> u32 {
+        64 - (self.reserved_bits as u32
+            + self.datacenter_bits as u32
+            + self.worker_bits as u32
+            + self.sequence_bits as u32)
+    }
+
+    /// Checks that the bit widths leave room for a timestamp and the IDs fit their fields
+    pub fn validate(&self) -> Result<(), IDError> {
+        let field_bits =
+            self.datacenter_bits as u32 + self.worker_bits as u32 + self.sequence_bits as u32;
+        if field_bits > 32 {
+            return Err(IDError::InvalidSnowflake(format!(
+                "Snowflake datacenter, worker and sequence bits must total at most 32, got {}",
+                field_bits
+            )));
+        }
+        if self.reserved_bits as u32 + field_bits >= 64 {
+            return Err(IDError::InvalidSnowflake(format!(
+                "Snowflake reserved bits leave no room for a timestamp, got {}",
+                self.reserved_bits
+            )));
+        }
+        if self.sequence_bits == 0 {
+            return Err(IDError::InvalidSnowflake(
+                "Snowflake sequence needs at least 1 bit".to_string(),
+            ));
+        }
+        if self.datacenter_id >> self.datacenter_bits != 0 {
+            return Err(IDError::InvalidSnowflake(format!(
+                "Snowflake datacenter ID {} does not fit in {} bits",
+                self.datacenter_id, self.datacenter_bits
+            )));
+        }
+        if self.worker_id >> self.worker_bits != 0 {
+            return Err(IDError::InvalidSnowflake(format!(
+                "Snowflake worker ID {} does not fit in {} bits",
+                self.worker_id, self.worker_bits
+            )));
+        }
+        Ok(())
+    }
+
+    /// Packs the fields into an ID. `timestamp` is in milliseconds since the Unix epoch.
+    pub fn encode(&self, timestamp: u64, sequence: u64) -> Result<u64, IDError> {
+        let elapsed = timestamp.checked_sub(self.epoch).ok_or_else(|| {
+            IDError::InvalidSnowflake(format!(
+                "Timestamp {} is before the Snowflake epoch {}",
+                timestamp, self.epoch
+            ))
+        })?;
+        if elapsed >> self.timestamp_bits() != 0 {
+            return Err(IDError::InvalidSnowflake(format!(
+                "Timestamp {} overflows the {}-bit Snowflake timestamp field",
+                timestamp,
+                self.timestamp_bits()
+            )));
+        }
+
+        let worker_shift = self.sequence_bits as u32;
+        let datacenter_shift = worker_shift + self.worker_bits as u32;
+        let timestamp_shift = datacenter_shift + self.datacenter_bits as u32;
+        Ok(elapsed << timestamp_shift
+            | self.datacenter_id << datacenter_shift
+            | self.worker_id << worker_shift
+            | sequence)
+    }
+
+    /// Splits an ID into its timestamp, datacenter, worker and sequence
+    pub fn decode(&self, id: u64) -> SnowflakeParts {
+        let worker_shift = self.sequence_bits as u32;
+        let datacenter_shift = worker_shift + self.worker_bits as u32;
+        let timestamp_shift = datacenter_shift + self.datacenter_bits as u32;
+        SnowflakeParts {
+            timestamp: (id >> timestamp_shift) + self.epoch,
+            datacenter_id: (id >> datacenter_shift) & mask(self.datacenter_bits),
+            worker_id: (id >> worker_shift) & mask(self.worker_bits),
+            sequence: id & mask(self.sequence_bits),
+        }
+    }
+}
+
+fn mask(bits: u8) -> u64 {
+    (1u64 << bits) - 1
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/**
+ * Returns the next Snowflake ID for the given layout
+ *
+ * IDs issued in the same millisecond get increasing sequence numbers. When the
+ * sequence is exhausted, this waits for the next millisecond.
+ *
+ * # Arguments
+ *
+ * * `config` - The layout and identity of the generator
+ */
+pub fn next_snowflake(config: &SnowflakeConfig) -> Result<u64, IDError> {
+    config.validate()?;
+
+    let mut last = LAST_ISSUED.lock().unwrap_or_else(|e| e.into_inner());
+    let (last_ms, last_seq) = *last;
+    // Never go backwards, even if the system clock does
+    let mut now = now_millis().max(last_ms);
+    let mut sequence = 0;
+
+    if now == last_ms {
+        sequence = last_seq + 1;
+        if sequence > mask(config.sequence_bits) {
+            while now <= last_ms {
+                std::thread::yield_now();
+                now = now_millis();
+            }
+            sequence = 0;
+        }
+    }
+
+    let id = config.encode(now, sequence)?;
+    *last = (now, sequence);
+    Ok(id)
+}
+
+/**
+ * Returns a Snowflake ID stamped with an explicit time, for backfilling
+ *
+ * IDs issued for the same millisecond get increasing sequence numbers, even
+ * when other timestamps come in between, so one entry is kept per millisecond
+ * used. Unlike `next_snowflake`, this cannot wait for the clock, so running out
+ * of sequence numbers is an error.
+ *
+ * # Arguments
+ *
+ * * `config` - The layout and identity of the generator
+ * * `timestamp` - Milliseconds since the Unix epoch
+ */
+pub fn snowflake_at(config: &SnowflakeConfig, timestamp: u64) -> Result<u64, IDError> {
+    /// Last sequence issued for each explicit Unix millisecond
+    static ISSUED_AT: OnceLock<Mutex<HashMap<u64, u64>>> = OnceLock::new();
+    config.validate()?;
+
+    let mut issued = ISSUED_AT
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let sequence = issued.get(&timestamp).map_or(0, |last| last + 1);
+    if sequence > mask(config.sequence_bits) {
+        return Err(IDError::InvalidSnowflake(format!(
+            "Snowflake sequence exhausted for timestamp {}",
+            timestamp
+        )));
+    }
+
+    let id = config.encode(timestamp, sequence)?;
+    issued.insert(timestamp, sequence);
+    Ok(id)
+}
@@ -0,0 +1,85 @@
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Longest single sleep, so a stop is noticed quickly even at low rates
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Set once stdin closes or a termination signal arrives
+static STOPPED: AtomicBool = AtomicBool::new(false);
+
+/**
+ * Starts watching for the end of a stream
+ *
+ * The stream stops when stdin reaches end of file, or on SIGINT, SIGTERM or
+ * SIGHUP (Ctrl+C or Ctrl+Break on Windows). Anything read from stdin is
+ * discarded.
+ */
+pub fn watch_for_stop() -> Result<(), String> {
+    ctrlc::set_handler(|| STOPPED.store(true, Ordering::Relaxed))
+        .map_err(|err| format!("Cannot install the signal handler: {}", err))?;
+    thread::spawn(|| {
+        let _ = io::copy(&mut io::stdin().lock(), &mut io::sink());
+        STOPPED.store(true, Ordering::Relaxed);
+    });
+    Ok(())
+}
+
+/// Whether stdin has closed or a termination signal has arrived
+pub fn is_stopped() -> bool {
+    STOPPED.load(Ordering::Relaxed)
+}
+
+/// Paces an unbounded run of IDs, with an optional time limit and rate
+#[derive(Debug, Clone)]
+pub struct Stream {
+    started: Instant,
+    deadline: Option<Instant>,
+    /// IDs per second
+    rate: Option<f64>,
+}
+
+impl Stream {
+    /**
+     * Starts a stream now
+     *
+     * # Arguments
+     *
+     * * `duration` - How long to run for, or `None` to run until stopped
+     * * `rate` - The most IDs to emit per second, or `None` for no limit
+     */
+    pub fn new(duration: Option<Duration>, rate: Option<f64>) -> Self {
+        let started = Instant::now();
+        Stream {
+            started,
+            // A deadline too far off to represent is the same as none
+            deadline: duration.and_then(|duration| started.checked_add(duration)),
+            rate,
+        }
+    }
+
+    /// Waits until the ID at position `index` (from 0) is due. Returns false
+    /// once the stream should end instead.
+    pub fn next(&self, index: u64) -> bool {
+        // Due times are measured from the start, so sleeps do not drift. At a
+        // very low rate the ID may never be due; the stream then just waits to end.
+        let due = match self.rate {
+            Some(rate) => Duration::try_from_secs_f64(index as f64 / rate)
+                .ok()
+                .and_then(|offset| self.started.checked_add(offset)),
+            None => Some(self.started),
+        };
+        loop {
+            let now = Instant::now();
+            if is_stopped() || self.deadline.is_some_and(|deadline| now >= deadline) {
+                return false;
+            }
+            match due {
+                Some(due) if now >= due => return true,
+                Some(due) => thread::sleep((due - now).min(POLL_INTERVAL)),
+                None => thread::sleep(POLL_INTERVAL),
+            }
+        }
+    }
+}
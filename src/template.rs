@@ -0,0 +1,198 @@
+use crate::id::{new_id_at, IDError, IDFormat};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Utc};
+use std::time::SystemTime;
+
+/// Format of `{date}` when no strftime pattern is given
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
+
+/// An ID placeholder resolved to everything `new_id_at` needs
+#[derive(Debug, Clone)]
+pub struct IdPlaceholder {
+    pub format: IDFormat,
+    pub len: Option<usize>,
+    pub namespace: Option<String>,
+    pub name: Option<String>,
+}
+
+/// One piece of a parsed template
+#[derive(Debug, Clone)]
+pub enum Segment {
+    Literal(String),
+    /// An ID such as `{ulid}` or `{nanoid:6}`
+    Id(IdPlaceholder),
+    /// The running index, starting at 1, such as `{seq}` or `{seq:06}`
+    Seq {
+        width: usize,
+        zero_pad: bool,
+    },
+    /// The generation time as a strftime pattern, such as `{date:%Y%m%d}`
+    Date(String),
+}
+
+/// A composite ID pattern like `ord-{ulid}-{nanoid:6}`
+#[derive(Debug, Clone)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /**
+     * Parses a template
+     *
+     * Placeholders are written `{name}` or `{name:options}`, and `{{` and `}}`
+     * stand for literal braces. `seq` and `date` are handled here; every other
+     * name is passed to `resolve` along with its options.
+     *
+     * # Arguments
+     *
+     * * `template` - The template text
+     * * `resolve` - Turns an ID type name and its options into a placeholder
+     */
+    pub fn parse<F>(template: &str, mut resolve: F) -> Result<Self, IDError>
+    where
+        F: FnMut(&str, Option<&str>) -> Result<IdPlaceholder, IDError>,
+    {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.char_indices().peekable();
+
+        while let Some((start, c)) = chars.next() {
+            match c {
+                '{' if chars.peek().map(|(_, c)| *c) == Some('{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek().map(|(_, c)| *c) == Some('}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let end = template[start..].find('}').ok_or_else(|| {
+                        IDError::InvalidTemplate(format!(
+                            "Unclosed '{{' at position {} in template '{}'",
+                            start + 1,
+                            template
+                        ))
+                    })? + start;
+                    while chars.peek().is_some_and(|(i, _)| *i <= end) {
+                        chars.next();
+                    }
+
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    let placeholder = &template[start + 1..end];
+                    segments.push(parse_placeholder(placeholder, &mut resolve)?);
+                }
+                '}' => {
+                    return Err(IDError::InvalidTemplate(format!(
+                        "Unmatched '}}' at position {} in template '{}'; write '}}}}' for a literal brace",
+                        start + 1,
+                        template
+                    )))
+                }
+                _ => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Template { segments })
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Whether the output depends on the current time, through `{date}` or a
+    /// time-based ID
+    pub fn uses_time(&self) -> bool {
+        self.segments.iter().any(|segment| match segment {
+            Segment::Id(placeholder) => placeholder.format.has_timestamp(),
+            Segment::Date(_) => true,
+            _ => false,
+        })
+    }
+
+    /**
+     * Renders one ID from the template
+     *
+     * # Arguments
+     *
+     * * `index` - The value of `{seq}`
+     * * `at` - The time for `{date}` and time-based IDs instead of now
+     */
+    pub fn render(&self, index: u64, at: Option<SystemTime>) -> Result<String, IDError> {
+        let mut output = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => output.push_str(text),
+                Segment::Id(placeholder) => output.push_str(&new_id_at(
+                    &placeholder.format,
+                    placeholder.len,
+                    placeholder.namespace.as_deref(),
+                    placeholder.name.as_deref(),
+                    at.filter(|_| placeholder.format.has_timestamp()),
+                )?),
+                Segment::Seq { width, zero_pad } => {
+                    if *zero_pad {
+                        output.push_str(&format!("{:0width$}", index, width = *width));
+                    } else {
+                        output.push_str(&format!("{:width$}", index, width = *width));
+                    }
+                }
+                Segment::Date(pattern) => {
+                    let time: DateTime<Utc> = at.unwrap_or_else(SystemTime::now).into();
+                    output.push_str(&time.format(pattern).to_string());
+                }
+            }
+        }
+        Ok(output)
+    }
+}
+
+fn parse_placeholder<F>(placeholder: &str, resolve: &mut F) -> Result<Segment, IDError>
+where
+    F: FnMut(&str, Option<&str>) -> Result<IdPlaceholder, IDError>,
+{
+    let (name, options) = match placeholder.split_once(':') {
+        Some((name, options)) => (name, Some(options)),
+        None => (placeholder, None),
+    };
+
+    match name {
+        "seq" => {
+            let options = options.unwrap_or("");
+            let width = if options.is_empty() {
+                0
+            } else {
+                options.parse().map_err(|_| {
+                    IDError::InvalidTemplate(format!(
+                        "Invalid {{seq:{}}}: the option must be a width such as 6, or 06 to pad with zeros",
+                        options
+                    ))
+                })?
+            };
+            Ok(Segment::Seq {
+                width,
+                zero_pad: options.starts_with('0'),
+            })
+        }
+        "date" => {
+            let pattern = options.unwrap_or(DEFAULT_DATE_FORMAT);
+            if StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error)) {
+                return Err(IDError::InvalidTemplate(format!(
+                    "Invalid {{date:{}}}: not a valid strftime pattern",
+                    pattern
+                )));
+            }
+            Ok(Segment::Date(pattern.to_string()))
+        }
+        "" => Err(IDError::InvalidTemplate(
+            "Empty placeholder '{}'; write '{{' for a literal brace".to_string(),
+        )),
+        _ => Ok(Segment::Id(resolve(name, options)?)),
+    }
+}
@@ -0,0 +1,141 @@
+use crate::id::IDError;
+use std::fmt;
+use uuid::Uuid;
+
+/// Maximum length of a TypeID type prefix
+pub const MAX_PREFIX_LENGTH: usize = 63;
+
+/// Length of the base32 suffix
+pub const SUFFIX_LENGTH: usize = 26;
+
+const ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+
+/// A TypeID: an optional type prefix plus a UUID encoded in lowercase Crockford base32
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeId {
+    pub prefix: String,
+    pub uuid: Uuid,
+}
+
+impl TypeId {
+    /// Creates a TypeID with a fresh UUID v7
+    pub fn new(prefix: &str) -> Result<Self, IDError> {
+        validate_prefix(prefix)?;
+        Ok(TypeId {
+            prefix: prefix.to_string(),
+            uuid: Uuid::now_v7(),
+        })
+    }
+
+    /// Creates a TypeID from an existing UUID
+    pub fn from_uuid(prefix: &str, uuid: Uuid) -> Result<Self, IDError> {
+        validate_prefix(prefix)?;
+        Ok(TypeId {
+            prefix: prefix.to_string(),
+            uuid,
+        })
+    }
+
+    /// The base32 suffix
+    pub fn suffix(&self) -> String {
+        encode_suffix(&self.uuid)
+    }
+
+    /// Parses `prefix_suffix` (or a bare suffix), splitting on the last underscore
+    pub fn parse(s: &str) -> Result<Self, IDError> {
+        let (prefix, suffix) = match s.rsplit_once('_') {
+            Some(("", _)) => {
+                return Err(IDError::InvalidTypeId(
+                    "TypeID prefix must not be empty when a separator is present".to_string(),
+                ))
+            }
+            Some((prefix, suffix)) => (prefix, suffix),
+            None => ("", s),
+        };
+        validate_prefix(prefix)?;
+        Ok(TypeId {
+            prefix: prefix.to_string(),
+            uuid: decode_suffix(suffix)?,
+        })
+    }
+}
+
+impl fmt::Display for TypeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.prefix.is_empty() {
+            write!(f, "{}", self.suffix())
+        } else {
+            write!(f, "{}_{}", self.prefix, self.suffix())
+        }
+    }
+}
+
+/// Checks a type prefix: at most 63 lowercase ASCII letters and underscores,
+/// starting and ending with a letter. An empty prefix is allowed.
+pub fn validate_prefix(prefix: &str) -> Result<(), IDError> {
+    if prefix.len() > MAX_PREFIX_LENGTH {
+        return Err(IDError::InvalidTypeId(format!(
+            "TypeID prefix must be at most {} characters, got {}",
+            MAX_PREFIX_LENGTH,
+            prefix.len()
+        )));
+    }
+    if let Some(c) = prefix
+        .chars()
+        .find(|c| !c.is_ascii_lowercase() && *c != '_')
+    {
+        return Err(IDError::InvalidTypeId(format!(
+            "TypeID prefix '{}' contains '{}'; only lowercase a-z and '_' are allowed",
+            prefix, c
+        )));
+    }
+    if prefix.starts_with('_') || prefix.ends_with('_') {
+        return Err(IDError::InvalidTypeId(format!(
+            "TypeID prefix '{}' must start and end with a letter",
+            prefix
+        )));
+    }
+    Ok(())
+}
+
+/// Encodes the 128 UUID bits as 26 base32 characters (with two leading zero bits)
+pub fn encode_suffix(uuid: &Uuid) -> String {
+    let value = uuid.as_u128();
+    (0..SUFFIX_LENGTH)
+        .map(|i| {
+            let shift = 5 * (SUFFIX_LENGTH - 1 - i);
+            ALPHABET[((value >> shift) & 0x1F) as usize] as char
+        })
+        .collect()
+}
+
+/// Decodes a 26-character base32 suffix back into a UUID
+pub fn decode_suffix(suffix: &str) -> Result<Uuid, IDError> {
+    if suffix.len() != SUFFIX_LENGTH {
+        return Err(IDError::InvalidTypeId(format!(
+            "TypeID suffix must be {} characters, got {}",
+            SUFFIX_LENGTH,
+            suffix.len()
+        )));
+    }
+
+    let mut value: u128 = 0;
+    for (i, c) in suffix.bytes().enumerate() {
+        let digit = ALPHABET.iter().position(|&b| b == c).ok_or_else(|| {
+            IDError::InvalidTypeId(format!(
+                "TypeID suffix contains invalid character '{}' at position {}",
+                c as char,
+                i + 1
+            ))
+        })?;
+        // The first character only carries 3 bits; anything above '7' overflows 128 bits
+        if i == 0 && digit > 7 {
+            return Err(IDError::InvalidTypeId(format!(
+                "TypeID suffix '{}' overflows 128 bits (must start with 0-7)",
+                suffix
+            )));
+        }
+        value = value << 5 | digit as u128;
+    }
+    Ok(Uuid::from_u128(value))
+}
@@ -0,0 +1,136 @@
+use crate::rng;
+use std::fmt;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Length of the base32hex string form
+pub const XID_LENGTH: usize = 20;
+
+const ALPHABET: &[u8; 32] = b"0123456789abcdefghijklmnopqrstuv";
+
+/// A globally unique ID in the rs/xid format: 4-byte timestamp, 3-byte machine ID,
+/// 2-byte process ID and 3-byte counter, encoded as 20 lowercase base32hex characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Xid {
+    /// Seconds since the Unix epoch
+    pub timestamp: u32,
+    pub machine_id: [u8; 3],
+    pub pid: u16,
+    /// 24-bit counter
+    pub counter: u32,
+}
+
+/// Random per-process machine ID, generated once like the ObjectId process value
+fn machine_id() -> [u8; 3] {
+    static MACHINE_ID: OnceLock<[u8; 3]> = OnceLock::new();
+    *MACHINE_ID.get_or_init(rng::random)
+}
+
+/// The process ID, or a random stand-in when seeded so the output is reproducible
+fn process_id() -> u16 {
+    static PID: OnceLock<u16> = OnceLock::new();
+    *PID.get_or_init(|| {
+        if rng::is_seeded() {
+            rng::random()
+        } else {
+            std::process::id() as u16
+        }
+    })
+}
+
+fn next_counter() -> u32 {
+    static COUNTER: OnceLock<AtomicU32> = OnceLock::new();
+    COUNTER
+        .get_or_init(|| AtomicU32::new(rng::random::<u32>()))
+        .fetch_add(1, Ordering::Relaxed)
+        & 0x00FF_FFFF
+}
+
+impl Xid {
+    /// Creates an xid for the current time
+    pub fn new() -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as u32)
+            .unwrap_or(0);
+        Xid {
+            timestamp: now,
+            machine_id: machine_id(),
+            pid: process_id(),
+            counter: next_counter(),
+        }
+    }
+
+    /// Creates an xid for the given Unix time (in seconds).
+    /// Returns `None` if the time does not fit in 32 bits.
+    pub fn from_unix(seconds: u64) -> Option<Self> {
+        Some(Xid {
+            timestamp: u32::try_from(seconds).ok()?,
+            ..Xid::new()
+        })
+    }
+
+    /// The raw 12 bytes
+    pub fn to_bytes(&self) -> [u8; 12] {
+        let mut bytes = [0u8; 12];
+        bytes[..4].copy_from_slice(&self.timestamp.to_be_bytes());
+        bytes[4..7].copy_from_slice(&self.machine_id);
+        bytes[7..9].copy_from_slice(&self.pid.to_be_bytes());
+        bytes[9..].copy_from_slice(&self.counter.to_be_bytes()[1..]);
+        bytes
+    }
+
+    /// Builds an xid from its raw 12 bytes
+    pub fn from_bytes(bytes: [u8; 12]) -> Self {
+        Xid {
+            timestamp: u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            machine_id: [bytes[4], bytes[5], bytes[6]],
+            pid: u16::from_be_bytes([bytes[7], bytes[8]]),
+            counter: u32::from_be_bytes([0, bytes[9], bytes[10], bytes[11]]),
+        }
+    }
+
+    /// Parses the 20-character lowercase base32hex form
+    pub fn parse(s: &str) -> Option<Self> {
+        if s.len() != XID_LENGTH {
+            return None;
+        }
+
+        // 20 characters carry 100 bits: the 96 ID bits followed by 4 zero bits
+        let mut value: u128 = 0;
+        for c in s.bytes() {
+            let digit = ALPHABET.iter().position(|&b| b == c)?;
+            value = value << 5 | digit as u128;
+        }
+        if value & 0x0F != 0 {
+            return None;
+        }
+
+        let mut bytes = [0u8; 12];
+        bytes.copy_from_slice(&(value >> 4).to_be_bytes()[4..]);
+        Some(Xid::from_bytes(bytes))
+    }
+}
+
+impl Default for Xid {
+    fn default() -> Self {
+        Xid::new()
+    }
+}
+
+impl fmt::Display for Xid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut padded = [0u8; 16];
+        padded[4..].copy_from_slice(&self.to_bytes());
+        let value = u128::from_be_bytes(padded) << 4;
+
+        let encoded: String = (0..XID_LENGTH)
+            .map(|i| {
+                let shift = 5 * (XID_LENGTH - 1 - i);
+                ALPHABET[((value >> shift) & 0x1F) as usize] as char
+            })
+            .collect();
+        f.write_str(&encoded)
+    }
+}
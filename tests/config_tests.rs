@@ -0,0 +1,176 @@
+use idgen_cli::cli::{IdType, OutputFormat};
+use idgen_cli::config::{load, Config, ConfigFile, SettingSource};
+use std::ffi::OsString;
+use std::fs;
+use std::path::PathBuf;
+
+/// Writes `contents` to a temporary config file and loads it
+fn config_file(name: &str, contents: &str) -> ConfigFile {
+    let path =
+        std::env::temp_dir().join(format!("idgen-config-{}-{}.toml", name, std::process::id()));
+    fs::write(&path, contents).unwrap();
+    let file = ConfigFile::load(&path).unwrap().unwrap();
+    fs::remove_file(&path).unwrap();
+    file
+}
+
+fn args(args: &[&str]) -> Vec<OsString> {
+    std::iter::once("idgen")
+        .chain(args.iter().copied())
+        .map(OsString::from)
+        .collect()
+}
+
+fn source_of(config: &Config, name: &str) -> SettingSource {
+    config
+        .settings
+        .iter()
+        .find(|setting| setting.name == name)
+        .unwrap_or_else(|| panic!("no setting {}", name))
+        .source
+        .clone()
+}
+
+const USER: &str = r#"
+type = "nanoid"
+length = 12
+json = true
+
+[namespaces]
+billing = "c3d5c1a4-5b0e-4b1a-9e0b-3f2b7f1b2a10"
+
+[profiles.invoice]
+prefix = "inv_"
+length = 16
+"#;
+
+// ============================================
+// Precedence Tests
+// ============================================
+
+#[test]
+fn test_config_defaults() {
+    let user = config_file("defaults", USER);
+    let config = load(args(&[]), std::slice::from_ref(&user)).unwrap();
+
+    assert!(matches!(config.cli.id_type, IdType::NanoId));
+    assert_eq!(config.cli.length, Some(12));
+    assert!(config.cli.json);
+    assert_eq!(
+        source_of(&config, "length"),
+        SettingSource::Config(user.path.clone())
+    );
+    assert_eq!(source_of(&config, "count"), SettingSource::Default);
+}
+
+#[test]
+fn test_config_command_line_wins() {
+    let user = config_file("command-line", USER);
+    let config = load(
+        args(&["-l", "8", "--output-format", "csv"]),
+        std::slice::from_ref(&user),
+    )
+    .unwrap();
+
+    assert_eq!(config.cli.length, Some(8));
+    assert_eq!(source_of(&config, "length"), SettingSource::CommandLine);
+    // json = true conflicts with --output-format, so it is left out
+    assert!(!config.cli.json);
+    assert_eq!(config.cli.output_format, Some(OutputFormat::Csv));
+}
+
+#[test]
+fn test_config_project_over_user() {
+    let user = config_file("user", USER);
+    let project = config_file("project", "length = 10\njson = false\n");
+    let config = load(args(&[]), &[user, project.clone()]).unwrap();
+
+    assert_eq!(config.cli.length, Some(10));
+    assert_eq!(
+        source_of(&config, "length"),
+        SettingSource::Config(project.path.clone())
+    );
+    // false still overrides the user config
+    assert!(!config.cli.json);
+    assert!(matches!(config.cli.id_type, IdType::NanoId));
+}
+
+#[test]
+fn test_config_profile() {
+    let user = config_file("profile", USER);
+    let project = config_file("profile-project", "length = 10\n");
+    let config = load(args(&["--profile", "invoice"]), &[user.clone(), project]).unwrap();
+
+    assert_eq!(config.cli.prefix, "inv_");
+    // The profile beats the project config's top level
+    assert_eq!(config.cli.length, Some(16));
+    assert_eq!(
+        source_of(&config, "prefix"),
+        SettingSource::Profile("invoice".to_string(), user.path.clone())
+    );
+}
+
+// ============================================
+// Error Tests
+// ============================================
+
+#[test]
+fn test_config_unknown_profile() {
+    let user = config_file("unknown-profile", USER);
+    let err = load(args(&["--profile", "nope"]), &[user]).unwrap_err();
+    assert!(err.contains("Unknown profile 'nope'"), "{}", err);
+    assert!(err.contains("invoice"), "{}", err);
+}
+
+#[test]
+fn test_config_unknown_setting() {
+    let file = config_file("unknown-setting", "lenght = 5\n");
+    let err = load(args(&[]), &[file]).unwrap_err();
+    assert!(err.contains("Unknown setting 'lenght'"), "{}", err);
+}
+
+#[test]
+fn test_config_invalid_values() {
+    for (name, contents) in [
+        ("flag", "json = \"yes\"\n"),
+        ("list", "length = [1, 2]\n"),
+        ("profile", "profile = \"invoice\"\n"),
+    ] {
+        let file = config_file(name, contents);
+        assert!(load(args(&[]), &[file]).is_err(), "{}", contents);
+    }
+}
+
+#[test]
+fn test_config_project_cannot_touch_files() {
+    let dir = std::env::temp_dir().join(format!("idgen-project-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(".idgen.toml");
+    for (contents, command_line) in [
+        ("output = \"ids.txt\"\n", &[][..]),
+        ("names-from = \"names.txt\"\n", &[][..]),
+        (
+            "[profiles.state]\nstate-file = \"ulid.state\"\n",
+            &["--profile", "state"][..],
+        ),
+    ] {
+        fs::write(&path, contents).unwrap();
+        let project = ConfigFile::load(&path).unwrap().unwrap();
+        assert!(project.is_project());
+        let err = load(args(command_line), &[project]).unwrap_err();
+        assert!(err.contains("is not allowed"), "{}", err);
+    }
+    fs::remove_dir_all(&dir).unwrap();
+
+    // The user config may set them
+    let user = config_file("user-output", "output = \"ids.txt\"\nappend = true\n");
+    assert!(!user.is_project());
+    let config = load(args(&[]), &[user]).unwrap();
+    assert!(config.cli.append);
+}
+
+#[test]
+fn test_config_missing_file() {
+    let path = PathBuf::from("/nonexistent/idgen/config.toml");
+    assert!(ConfigFile::load(&path).unwrap().is_none());
+}
@@ -14,175 +14,1346 @@ fn idgen_bin() -> std::path::PathBuf {
     path
 }
 
+/// An idgen command that ignores the user and project config files, so local
+/// defaults can't change the results
+fn idgen() -> Command {
+    let mut command = Command::new(idgen_bin());
+    command.env("IDGEN_NO_CONFIG", "1");
+    command
+}
+
+// ============================================
+// Success Exit Code (0) Tests
+// ============================================
+
+#[test]
+fn test_exit_code_success_default() {
+    let output = idgen().output().expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_success_uuid_v4() {
+    let output = idgen()
+        .args(["-t", "uuid4"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_success_uuid_v6() {
+    let output = idgen()
+        .args(["-t", "uuid6"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_success_convert_v1_to_v6() {
+    let output = idgen()
+        .args(["convert", "c232ab00-9414-11ec-b3c8-9f6bdeced846"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim(), "1ec9414c-232a-6b00-b3c8-9f6bdeced846");
+}
+
+#[test]
+fn test_exit_code_success_convert_v6_to_v1() {
+    let output = idgen()
+        .args([
+            "convert",
+            "1ec9414c-232a-6b00-b3c8-9f6bdeced846",
+            "-f",
+            "simple",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim(), "c232ab00941411ecb3c89f6bdeced846");
+}
+
+#[test]
+fn test_exit_code_success_uuid_v7() {
+    let output = idgen()
+        .args(["-t", "uuid7"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_success_uuid_v8_payload() {
+    let output = idgen()
+        .args([
+            "-t",
+            "uuid8",
+            "--payload",
+            "00112233445566778899aabbccddeeff",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim(), "00112233-4455-8677-8899-aabbccddeeff");
+}
+
+#[test]
+fn test_exit_code_success_nanoid() {
+    let output = idgen()
+        .args(["-t", "nanoid"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_success_nanoid_alphabet_preset() {
+    let output = idgen()
+        .args(["-t", "nanoid", "-a", "numbers", "-l", "6"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let id = stdout.trim();
+    assert_eq!(id.len(), 6);
+    assert!(id.chars().all(|c| c.is_ascii_digit()));
+}
+
+#[test]
+fn test_exit_code_success_ulid() {
+    let output = idgen()
+        .args(["-t", "ulid"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_success_ulid_at() {
+    let output = idgen()
+        .args(["-t", "ulid", "--at", "2024-01-31T12:00:00Z"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // 2024-01-31T12:00:00Z is 1706702400000 ms, which encodes as 01HNFP7TG0
+    assert!(stdout.trim().starts_with("01HNFP7TG0"));
+}
+
+#[test]
+fn test_exit_code_success_from_to_even_spread() {
+    let output = idgen()
+        .args([
+            "-t",
+            "objectid",
+            "-c",
+            "3",
+            "--from",
+            "1700000000",
+            "--to",
+            "1700000100",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let seconds: Vec<u32> = stdout
+        .lines()
+        .map(|id| u32::from_str_radix(&id[..8], 16).unwrap())
+        .collect();
+    assert_eq!(seconds, vec![1_700_000_000, 1_700_000_050, 1_700_000_100]);
+}
+
+#[test]
+fn test_exit_code_success_from_to_random_sorted() {
+    let output = idgen()
+        .args([
+            "-t",
+            "ulid",
+            "-c",
+            "50",
+            "--from",
+            "2020-01-01T00:00:00Z",
+            "--to",
+            "2024-01-01T00:00:00Z",
+            "--spread",
+            "random",
+            "--sorted",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // The first 10 characters of a ULID are its timestamp
+    let times: Vec<&str> = stdout.lines().map(|id| &id[..10]).collect();
+    assert_eq!(times.len(), 50);
+    assert!(times.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert!(times[0] >= "01DXF6DT00" && times[49] <= "01HK153X00");
+}
+
+#[test]
+fn test_exit_code_success_from_to_random_clock_seq() {
+    let output = idgen()
+        .args([
+            "-t",
+            "uuid1",
+            "--clock-seq",
+            "5",
+            "-c",
+            "6",
+            "--from",
+            "2020-01-01T00:00:00Z",
+            "--to",
+            "2024-01-01T00:00:00Z",
+            "--spread",
+            "random",
+            "--seed",
+            "7",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let seconds: Vec<u64> = stdout
+        .lines()
+        .map(|id| {
+            let uuid = uuid::Uuid::parse_str(id).unwrap();
+            uuid.get_timestamp().unwrap().to_unix().0
+        })
+        .collect();
+    assert_eq!(seconds.len(), 6);
+    // 2020-01-01 to 2024-01-01, in the order drawn rather than clamped upwards
+    assert!(seconds
+        .iter()
+        .all(|s| (1_577_836_800..=1_704_067_200).contains(s)));
+    assert!(seconds.windows(2).any(|pair| pair[0] > pair[1]));
+}
+
+#[test]
+fn test_exit_code_success_monotonic_ulid_state_file() {
+    let state_file =
+        std::env::temp_dir().join(format!("idgen-monotonic-{}.state", std::process::id()));
+    let _ = std::fs::remove_file(&state_file);
+
+    let run = || {
+        let output = idgen()
+            .args(["-t", "ulid", "--monotonic", "-c", "3", "--state-file"])
+            .arg(&state_file)
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(0));
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+    let mut ids = run();
+    ids.extend(run());
+
+    assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    let saved = std::fs::read_to_string(&state_file).unwrap();
+    assert_eq!(saved.trim(), ids[5]);
+    // The state is written to a temporary file and renamed into place
+    let temp_prefix = format!(".{}.", state_file.file_name().unwrap().to_string_lossy());
+    assert!(!std::fs::read_dir(std::env::temp_dir())
+        .unwrap()
+        .filter_map(Result::ok)
+        .any(|entry| entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(&temp_prefix)));
+    let _ = std::fs::remove_file(&state_file);
+}
+
+#[test]
+fn test_exit_code_success_objectid() {
+    let output = idgen()
+        .args(["-t", "objectid"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_success_snowflake() {
+    let output = idgen()
+        .args(["-t", "snowflake", "--preset", "discord", "--worker-id", "3"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.trim().parse::<u64>().is_ok());
+}
+
+#[test]
+fn test_exit_code_success_inspect_snowflake() {
+    let output = idgen()
+        .args(["inspect", "175928847299117063", "--snowflake", "discord"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Snowflake"));
+    assert!(stdout.contains("2016-04-30T11:18:25.796"));
+}
+
+#[test]
+fn test_exit_code_success_ksuid() {
+    let output = idgen()
+        .args(["-t", "ksuid"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim().len(), 27);
+}
+
+#[test]
+fn test_exit_code_success_typeid() {
+    let output = idgen()
+        .args(["-t", "typeid", "--type-prefix", "user"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("user_"));
+}
+
+#[test]
+fn test_exit_code_success_xid() {
+    let output = idgen()
+        .args(["-t", "xid"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim().len(), 20);
+}
+
+#[test]
+fn test_exit_code_success_cuid1() {
+    let output = idgen()
+        .args(["-t", "cuid1"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_success_cuid2() {
+    let output = idgen()
+        .args(["-t", "cuid2"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_success_help() {
+    let output = idgen()
+        .args(["--help"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_success_version() {
+    let output = idgen()
+        .args(["--version"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_success_json() {
+    let output = idgen()
+        .args(["--json"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_success_inspect_valid_uuid() {
+    let output = idgen()
+        .args(["inspect", "550e8400-e29b-44d4-a716-446655440000"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_success_uuid_v3_with_params() {
+    let output = idgen()
+        .args(["-t", "uuid3", "--namespace", "DNS", "--name", "test"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_success_uuid_v5_with_params() {
+    let output = idgen()
+        .args(["-t", "uuid5", "--namespace", "URL", "--name", "test"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_success_sqids_encode() {
+    let output = idgen()
+        .args(["encode", "1", "2", "3"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim(), "86Rf07");
+}
+
+#[test]
+fn test_exit_code_success_sqids_encode_each_min_length() {
+    let output = idgen()
+        .args(["encode", "1", "2", "--each", "--min-length", "10"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines.iter().all(|line| line.len() >= 10));
+}
+
+#[test]
+fn test_exit_code_success_sqids_decode() {
+    let output = idgen()
+        .args(["decode", "86Rf07"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim(), "1 2 3");
+}
+
+#[test]
+fn test_exit_code_success_sqids_custom_alphabet_round_trip() {
+    let alphabet = "k3G7QAe51FCsPW92uEOyq4Bg6Sp8YzVTmnU0liwDdHXLajZrfxNhobJIRcMvKt";
+    let encoded = idgen()
+        .args(["encode", "1", "2", "3", "--alphabet", alphabet])
+        .output()
+        .expect("Failed to execute command");
+    assert!(encoded.status.success());
+    let id = String::from_utf8_lossy(&encoded.stdout).trim().to_string();
+    // Expected value from the Sqids spec test suite
+    assert_eq!(id, "XRKUdQ");
+
+    let decoded = idgen()
+        .args(["decode", &id, "--alphabet", alphabet])
+        .output()
+        .expect("Failed to execute command");
+    assert!(decoded.status.success());
+    assert_eq!(String::from_utf8_lossy(&decoded.stdout).trim(), "1 2 3");
+}
+
+#[test]
+fn test_exit_code_success_sqids_json() {
+    let output = idgen()
+        .args(["decode", "86Rf07", "--json"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["value"], "86Rf07");
+    assert_eq!(json[0]["numbers"], serde_json::json!([1, 2, 3]));
+}
+
+#[test]
+fn test_exit_code_success_names_from_stdin() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = idgen()
+        .args(["-t", "uuid5", "--namespace", "DNS", "--names-from", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"example.com\n\nexample.org\r\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "example.com\tcfbff0d1-9375-5685-968c-48ce8b15ae17",
+            "example.org\taad03681-8b63-5304-89e0-8ca8f49461b5",
+        ]
+    );
+}
+
+#[test]
+fn test_exit_code_success_names_from_file_csv() {
+    let names_file = std::env::temp_dir().join(format!("idgen-names-{}.txt", std::process::id()));
+    std::fs::write(&names_file, "example.com\nfoo,bar\n").unwrap();
+
+    let output = idgen()
+        .args(["-t", "uuid5", "--namespace", "DNS", "--csv", "--names-from"])
+        .arg(&names_file)
+        .output()
+        .expect("Failed to execute command");
+    let _ = std::fs::remove_file(&names_file);
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "name,uuid");
+    assert_eq!(lines[1], "example.com,cfbff0d1-9375-5685-968c-48ce8b15ae17");
+    assert!(lines[2].starts_with("\"foo,bar\","));
+}
+
+#[test]
+fn test_exit_code_success_names_from_json() {
+    let names_file =
+        std::env::temp_dir().join(format!("idgen-names-json-{}.txt", std::process::id()));
+    std::fs::write(&names_file, "example.com\n").unwrap();
+
+    let output = idgen()
+        .args([
+            "-t",
+            "uuid5",
+            "--namespace",
+            "DNS",
+            "--json",
+            "--names-from",
+        ])
+        .arg(&names_file)
+        .output()
+        .expect("Failed to execute command");
+    let _ = std::fs::remove_file(&names_file);
+
+    assert_eq!(output.status.code(), Some(0));
+    let records: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(records[0]["name"], "example.com");
+    assert_eq!(records[0]["uuid"], "cfbff0d1-9375-5685-968c-48ce8b15ae17");
+}
+
+#[test]
+fn test_exit_code_success_custom_namespace() {
+    let output = idgen()
+        .args([
+            "-t",
+            "uuid5",
+            "--namespace",
+            "dns-alias",
+            "--name",
+            "example.com",
+            "--define-namespace",
+            "dns-alias=6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim(), "cfbff0d1-9375-5685-968c-48ce8b15ae17");
+}
+
+#[test]
+fn test_exit_code_success_namespaces_from_config() {
+    let config_home = std::env::temp_dir().join(format!("idgen-xdg-{}", std::process::id()));
+    std::fs::create_dir_all(config_home.join("idgen")).unwrap();
+    std::fs::write(
+        config_home.join("idgen").join("config.toml"),
+        "[namespaces]\nbilling = \"c3d5c1a4-5b0e-4b1a-9e0b-3f2b7f1b2a10\"\n",
+    )
+    .unwrap();
+
+    let output = Command::new(idgen_bin())
+        .env("XDG_CONFIG_HOME", &config_home)
+        .current_dir(&config_home)
+        .args(["namespaces"])
+        .output()
+        .expect("Failed to execute command");
+    let _ = std::fs::remove_dir_all(&config_home);
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("DNS"));
+    assert!(stdout.contains("billing  c3d5c1a4-5b0e-4b1a-9e0b-3f2b7f1b2a10  config"));
+}
+
+#[test]
+fn test_exit_code_success_namespace_path_verbose() {
+    let output = idgen()
+        .args([
+            "-t",
+            "uuid5",
+            "--namespace",
+            "DNS:acme.com/orders",
+            "--name",
+            "42",
+            "--verbose",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim(), "90d3c1ca-dfae-5394-bf7f-2111d880b2bc");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("namespace DNS:acme.com = 3e38d44d-322e-53af-97b6-6204849f6a5c"));
+    assert!(stderr.contains("namespace DNS:acme.com/orders = e134628c-8e8a-5807-8fa2-9837e0d95e11"));
+}
+
+#[test]
+fn test_exit_code_success_seed_is_reproducible() {
+    let run = |seed: &str, args: &[&str]| {
+        idgen()
+            .args(["--seed", seed])
+            .args(args)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    for args in [
+        &["-c", "3"][..],
+        &["-t", "nanoid", "-c", "3"],
+        &["-t", "cuid2", "-c", "3"],
+        &["-t", "ulid", "-c", "3", "--at", "2024-01-01T00:00:00Z"],
+        &["-t", "objectid", "-c", "3", "--at", "1700000000"],
+    ] {
+        let first = run("42", args);
+        assert_eq!(first.status.code(), Some(0));
+        assert_eq!(first.stdout, run("42", args).stdout, "{:?}", args);
+        assert_ne!(first.stdout, run("43", args).stdout, "{:?}", args);
+
+        let stderr = String::from_utf8_lossy(&first.stderr);
+        assert!(stderr.contains("Warning: --seed"));
+    }
+}
+
+#[test]
+fn test_exit_code_success_template() {
+    let output = idgen()
+        .args([
+            "--template",
+            "ord-{date:%Y%m%d}-{seq:03}-{uuid5}",
+            "--namespace",
+            "DNS",
+            "--name",
+            "example.com",
+            "--at",
+            "2024-01-31T12:00:00Z",
+            "-c",
+            "2",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "ord-20240131-001-cfbff0d1-9375-5685-968c-48ce8b15ae17",
+            "ord-20240131-002-cfbff0d1-9375-5685-968c-48ce8b15ae17",
+        ]
+    );
+}
+
+#[test]
+fn test_exit_code_success_template_nanoid_options() {
+    let output = idgen()
+        .args(["--template", "{nanoid:4:01} {nanoid:01} {nanoid:6}"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parts: Vec<&str> = stdout.trim().split(' ').collect();
+    assert_eq!(parts[0].len(), 4);
+    assert!(parts[0].chars().all(|c| c == '0' || c == '1'));
+    // A lone digit alphabet keeps the default length of 21
+    assert_eq!(parts[1].len(), 21);
+    assert!(parts[1].chars().all(|c| c == '0' || c == '1'));
+    assert_eq!(parts[2].len(), 6);
+}
+
+#[test]
+fn test_exit_code_success_output_format_csv_columns() {
+    let output = idgen()
+        .args([
+            "-t",
+            "ulid",
+            "-c",
+            "2",
+            "--at",
+            "2024-01-31T12:00:00Z",
+            "--output-format",
+            "csv",
+            "--columns",
+            "type,timestamp",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "value,type,timestamp");
+    assert!(lines[1].ends_with(",ulid,2024-01-31T12:00:00+00:00"));
+}
+
+#[test]
+fn test_exit_code_success_output_format_ndjson() {
+    let output = idgen()
+        .args(["-c", "3", "--output-format", "ndjson", "--columns", "type"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let records: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0]["type"], "uuid4");
+    assert_eq!(records[0]["value"].as_str().unwrap().len(), 36);
+}
+
+#[test]
+fn test_exit_code_success_output_format_tsv_no_header() {
+    let output = idgen()
+        .args(["-c", "2", "--output-format", "tsv", "--no-header"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 2);
+}
+
+#[test]
+fn test_exit_code_success_inspect_output_format_csv() {
+    let output = idgen()
+        .args([
+            "inspect",
+            "01HNFR7SC0V54CYJ75JX66Q81M",
+            "--output-format",
+            "csv",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "id,valid,type,version,variant,timestamp");
+    assert_eq!(
+        lines[1],
+        "01HNFR7SC0V54CYJ75JX66Q81M,true,ULID,,,2024-01-31T12:34:56+00:00"
+    );
+}
+
+#[test]
+fn test_exit_code_success_json_large_count() {
+    let output = idgen()
+        .args(["-t", "nanoid", "-c", "20000", "--json"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let records: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(records.len(), 20000);
+    assert_eq!(records[19999]["value"].as_str().unwrap().len(), 21);
+}
+
+#[test]
+fn test_exit_code_success_threads_keep_order() {
+    let output = idgen()
+        .args([
+            "--template",
+            "{seq}-{nanoid:4}",
+            "-c",
+            "30000",
+            "--threads",
+            "4",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 30000);
+    for (i, line) in lines.iter().enumerate() {
+        assert!(line.starts_with(&format!("{}-", i + 1)), "{}", line);
+    }
+}
+
+#[test]
+fn test_exit_code_success_threads_json() {
+    let output = idgen()
+        .args(["-t", "ulid", "-c", "20000", "--threads", "3", "--json"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let records: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(records.len(), 20000);
+}
+
+#[test]
+fn test_exit_code_success_stats_on_stderr() {
+    let output = idgen()
+        .args(["-c", "10", "--threads", "2", "--stats"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 10);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("Generated 10 IDs in "), "{}", stderr);
+    assert!(stderr.contains("IDs/s, 2 threads)"), "{}", stderr);
+}
+
+#[test]
+fn test_exit_code_success_stream_duration_and_rate() {
+    use std::process::Stdio;
+
+    let mut child = idgen()
+        .args(["--stream", "--duration", "300ms", "--rate", "50/s"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    // Holding stdin open means only --duration can end the stream
+    let _stdin = child.stdin.take();
+    let output = child.wait_with_output().unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    let lines = String::from_utf8_lossy(&output.stdout).lines().count();
+    // One ID at the start, then one every 20ms
+    assert!((1..=16).contains(&lines), "{}", lines);
+}
+
+#[test]
+fn test_exit_code_success_stream_stdin_closed() {
+    use std::process::Stdio;
+
+    let mut child = idgen()
+        .args(["--stream", "--rate", "100/s", "--json"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    drop(child.stdin.take());
+    let output = child.wait_with_output().unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    // The JSON array is closed when the stream ends
+    let records: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(!records.is_empty());
+}
+
+#[test]
+fn test_exit_code_success_broken_pipe() {
+    use std::io::{BufRead, BufReader, Read};
+    use std::process::Stdio;
+
+    let mut child = idgen()
+        .args(["-c", "100000000"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    let mut first = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut first)
+        .unwrap();
+    // The reader is dropped here, like `| head -1` exiting
+    let status = child.wait().unwrap();
+    let mut stderr = String::new();
+    child
+        .stderr
+        .take()
+        .unwrap()
+        .read_to_string(&mut stderr)
+        .unwrap();
+
+    assert_eq!(first.trim().len(), 36);
+    assert_eq!(status.code(), Some(0));
+    assert_eq!(stderr, "");
+}
+
+#[test]
+fn test_exit_code_success_output_split_every() {
+    let dir = std::env::temp_dir().join(format!("idgen-split-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let output = idgen()
+        .args(["--template", "{seq}", "-c", "25", "--split-every", "10"])
+        .args(["--output-format", "csv", "-o"])
+        .arg(dir.join("ids.csv"))
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+    let mut names: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["ids-0001.csv", "ids-0002.csv", "ids-0003.csv"]);
+    // Every file is complete on its own, header included
+    assert_eq!(
+        std::fs::read_to_string(dir.join("ids-0003.csv")).unwrap(),
+        "value\n21\n22\n23\n24\n25\n"
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_exit_code_success_output_split_threads_json() {
+    let dir = std::env::temp_dir().join(format!("idgen-split-json-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let output = idgen()
+        .args([
+            "--template",
+            "{seq}",
+            "-c",
+            "25000",
+            "--split-every",
+            "10000",
+        ])
+        .args(["--threads", "3", "--json", "-o"])
+        .arg(dir.join("ids.json"))
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    for (part, first, len) in [(1, 1, 10000), (2, 10001, 10000), (3, 20001, 5000)] {
+        let path = dir.join(format!("ids-{:04}.json", part));
+        let records: Vec<serde_json::Value> =
+            serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+        assert_eq!(records.len(), len);
+        assert_eq!(records[0]["value"], first.to_string());
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_exit_code_success_output_append() {
+    let path = std::env::temp_dir().join(format!("idgen-append-{}.tsv", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    for _ in 0..2 {
+        let output = idgen()
+            .args(["-c", "2", "--output-format", "tsv", "--append", "-o"])
+            .arg(&path)
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(0));
+    }
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    // The header is only written to the empty file
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0], "value");
+    assert_eq!(lines.iter().filter(|line| **line == "value").count(), 1);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_exit_code_success_config_profile() {
+    let dir = std::env::temp_dir().join(format!("idgen-config-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("xdg").join("idgen")).unwrap();
+    std::fs::create_dir_all(dir.join("project")).unwrap();
+    std::fs::write(
+        dir.join("xdg").join("idgen").join("config.toml"),
+        "[profiles.invoice]\ntype = \"nanoid\"\nlength = 12\nprefix = \"inv_\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("project").join(".idgen.toml"), "count = 3\n").unwrap();
+
+    let run = |args: &[&str]| {
+        Command::new(idgen_bin())
+            .args(args)
+            .env("XDG_CONFIG_HOME", dir.join("xdg"))
+            .current_dir(dir.join("project"))
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run(&["--profile", "invoice"]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines
+        .iter()
+        .all(|line| line.starts_with("inv_") && line.len() == 16));
+
+    let output = run(&["--profile", "invoice", "config", "show", "--json"]);
+    assert_eq!(output.status.code(), Some(0));
+    let settings: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let source = |name: &str| {
+        settings
+            .iter()
+            .find(|setting| setting["name"] == name)
+            .map(|setting| setting["source"].as_str().unwrap().to_string())
+            .unwrap()
+    };
+    assert!(source("prefix").starts_with("profile 'invoice' in "));
+    assert!(source("count").ends_with(".idgen.toml"));
+    assert_eq!(source("format"), "default");
+
+    let output = run(&["--profile", "missing"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    // IDGEN_NO_CONFIG skips both files
+    let output = Command::new(idgen_bin())
+        .env("IDGEN_NO_CONFIG", "1")
+        .env("XDG_CONFIG_HOME", dir.join("xdg"))
+        .current_dir(dir.join("project"))
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 1);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// ============================================
+// Error Exit Code (1) Tests - Runtime Errors
+// ============================================
+
+#[test]
+fn test_exit_code_error_inspect_invalid_id() {
+    let output = idgen()
+        .args(["inspect", "not-a-valid-id"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_exit_code_error_sqids_decode_non_canonical() {
+    let output = idgen()
+        .args(["decode", "86Rf0"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_exit_code_error_output_missing_directory() {
+    let path = std::env::temp_dir()
+        .join(format!("idgen-missing-{}", std::process::id()))
+        .join("ids.txt");
+    let output = idgen()
+        .arg("-o")
+        .arg(&path)
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Cannot write"));
+}
+
+#[test]
+fn test_exit_code_error_names_from_missing_file() {
+    let output = idgen()
+        .args([
+            "-t",
+            "uuid5",
+            "--namespace",
+            "DNS",
+            "--names-from",
+            "/nonexistent/idgen-names.txt",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+}
+
 // ============================================
-// Success Exit Code (0) Tests
+// Usage Error Exit Code (2) Tests - Invalid Arguments
 // ============================================
 
 #[test]
-fn test_exit_code_success_default() {
-    let output = Command::new(idgen_bin())
+fn test_exit_code_usage_error_uuid_v3_missing_namespace() {
+    let output = idgen()
+        .args(["-t", "uuid3", "--name", "test"])
         .output()
         .expect("Failed to execute command");
 
-    assert!(output.status.success());
-    assert_eq!(output.status.code(), Some(0));
+    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(2));
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.to_lowercase().contains("namespace"));
 }
 
 #[test]
-fn test_exit_code_success_uuid_v4() {
-    let output = Command::new(idgen_bin())
-        .args(["-t", "uuid4"])
+fn test_exit_code_usage_error_uuid_v3_missing_name() {
+    let output = idgen()
+        .args(["-t", "uuid3", "--namespace", "DNS"])
         .output()
         .expect("Failed to execute command");
 
-    assert!(output.status.success());
-    assert_eq!(output.status.code(), Some(0));
+    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(2));
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.to_lowercase().contains("name"));
 }
 
 #[test]
-fn test_exit_code_success_nanoid() {
-    let output = Command::new(idgen_bin())
-        .args(["-t", "nanoid"])
+fn test_exit_code_usage_error_uuid_v3_missing_both() {
+    let output = idgen()
+        .args(["-t", "uuid3"])
         .output()
         .expect("Failed to execute command");
 
-    assert!(output.status.success());
-    assert_eq!(output.status.code(), Some(0));
+    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(2));
 }
 
 #[test]
-fn test_exit_code_success_ulid() {
-    let output = Command::new(idgen_bin())
-        .args(["-t", "ulid"])
+fn test_exit_code_usage_error_uuid_v5_missing_namespace() {
+    let output = idgen()
+        .args(["-t", "uuid5", "--name", "test"])
         .output()
         .expect("Failed to execute command");
 
-    assert!(output.status.success());
-    assert_eq!(output.status.code(), Some(0));
+    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(2));
 }
 
 #[test]
-fn test_exit_code_success_objectid() {
-    let output = Command::new(idgen_bin())
-        .args(["-t", "objectid"])
+fn test_exit_code_usage_error_uuid_v5_missing_name() {
+    let output = idgen()
+        .args(["-t", "uuid5", "--namespace", "DNS"])
         .output()
         .expect("Failed to execute command");
 
-    assert!(output.status.success());
-    assert_eq!(output.status.code(), Some(0));
+    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(2));
 }
 
 #[test]
-fn test_exit_code_success_cuid1() {
-    let output = Command::new(idgen_bin())
-        .args(["-t", "cuid1"])
+fn test_exit_code_usage_error_invalid_namespace_format() {
+    let output = idgen()
+        .args(["-t", "uuid3", "--namespace", "x", "--name", "test"])
         .output()
         .expect("Failed to execute command");
 
-    assert!(output.status.success());
-    assert_eq!(output.status.code(), Some(0));
+    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(2));
 }
 
 #[test]
-fn test_exit_code_success_cuid2() {
-    let output = Command::new(idgen_bin())
-        .args(["-t", "cuid2"])
+fn test_exit_code_usage_error_namespace_bad_character() {
+    let output = idgen()
+        .args([
+            "-t",
+            "uuid5",
+            "--namespace",
+            "6ba7b810-9dad-11d1-80b4-00c04fd430cg",
+            "--name",
+            "test",
+        ])
         .output()
         .expect("Failed to execute command");
 
-    assert!(output.status.success());
-    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid character 'g' at position 36"));
 }
 
 #[test]
-fn test_exit_code_success_help() {
-    let output = Command::new(idgen_bin())
-        .args(["--help"])
+fn test_exit_code_usage_error_namespace_multibyte() {
+    let output = idgen()
+        .args(["-t", "uuid5", "--namespace", "abcdefgh€", "--name", "test"])
         .output()
         .expect("Failed to execute command");
 
-    assert!(output.status.success());
-    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Invalid namespace UUID 'abcdefgh€'"));
 }
 
 #[test]
-fn test_exit_code_success_version() {
-    let output = Command::new(idgen_bin())
-        .args(["--version"])
+fn test_exit_code_usage_error_define_namespace_multibyte() {
+    let output = idgen()
+        .args([
+            "-t",
+            "uuid5",
+            "--define-namespace",
+            "x=abcdefgh€",
+            "--namespace",
+            "x",
+            "--name",
+            "test",
+        ])
         .output()
         .expect("Failed to execute command");
 
-    assert!(output.status.success());
-    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Invalid namespace UUID 'abcdefgh€'"));
 }
 
 #[test]
-fn test_exit_code_success_json() {
-    let output = Command::new(idgen_bin())
-        .args(["--json"])
+fn test_exit_code_usage_error_at_overflow() {
+    let output = idgen()
+        .args(["-t", "ulid", "--at", "18446744073709551615"])
         .output()
         .expect("Failed to execute command");
 
-    assert!(output.status.success());
-    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("too far in the future"));
 }
 
 #[test]
-fn test_exit_code_success_inspect_valid_uuid() {
-    let output = Command::new(idgen_bin())
-        .args(["inspect", "550e8400-e29b-44d4-a716-446655440000"])
+fn test_exit_code_usage_error_convert_v4() {
+    let output = idgen()
+        .args(["convert", "550e8400-e29b-44d4-a716-446655440000"])
         .output()
         .expect("Failed to execute command");
 
-    assert!(output.status.success());
-    assert_eq!(output.status.code(), Some(0));
+    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(2));
 }
 
 #[test]
-fn test_exit_code_success_uuid_v3_with_params() {
-    let output = Command::new(idgen_bin())
-        .args(["-t", "uuid3", "--namespace", "DNS", "--name", "test"])
+fn test_exit_code_usage_error_uuid_v8_field_too_wide() {
+    let output = idgen()
+        .args(["-t", "uuid8", "--custom-b", "0x1000"])
         .output()
         .expect("Failed to execute command");
 
-    assert!(output.status.success());
-    assert_eq!(output.status.code(), Some(0));
+    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(2));
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("custom_b"));
 }
 
 #[test]
-fn test_exit_code_success_uuid_v5_with_params() {
-    let output = Command::new(idgen_bin())
-        .args(["-t", "uuid5", "--namespace", "URL", "--name", "test"])
+fn test_exit_code_usage_error_snowflake_worker_id_too_large() {
+    let output = idgen()
+        .args(["-t", "snowflake", "--worker-id", "32"])
         .output()
         .expect("Failed to execute command");
 
-    assert!(output.status.success());
-    assert_eq!(output.status.code(), Some(0));
+    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(2));
 }
 
-// ============================================
-// Error Exit Code (1) Tests - Runtime Errors
-// ============================================
-
 #[test]
-fn test_exit_code_error_inspect_invalid_id() {
-    let output = Command::new(idgen_bin())
-        .args(["inspect", "not-a-valid-id"])
+fn test_exit_code_usage_error_typeid_invalid_prefix() {
+    let output = idgen()
+        .args(["-t", "typeid", "--type-prefix", "User"])
         .output()
         .expect("Failed to execute command");
 
     assert!(!output.status.success());
-    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(output.status.code(), Some(2));
 }
 
-// ============================================
-// Usage Error Exit Code (2) Tests - Invalid Arguments
-// ============================================
+#[test]
+fn test_exit_code_usage_error_sqids_short_alphabet() {
+    let output = idgen()
+        .args(["encode", "1", "--alphabet", "ab"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(2));
+}
 
 #[test]
-fn test_exit_code_usage_error_uuid_v3_missing_namespace() {
-    let output = Command::new(idgen_bin())
-        .args(["-t", "uuid3", "--name", "test"])
+fn test_exit_code_usage_error_nanoid_duplicate_alphabet() {
+    let output = idgen()
+        .args(["-t", "nanoid", "--alphabet", "aab"])
         .output()
         .expect("Failed to execute command");
 
@@ -190,13 +1361,13 @@ fn test_exit_code_usage_error_uuid_v3_missing_namespace() {
     assert_eq!(output.status.code(), Some(2));
 
     let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.to_lowercase().contains("namespace"));
+    assert!(stderr.contains("unique characters"));
 }
 
 #[test]
-fn test_exit_code_usage_error_uuid_v3_missing_name() {
-    let output = Command::new(idgen_bin())
-        .args(["-t", "uuid3", "--namespace", "DNS"])
+fn test_exit_code_usage_error_at_without_timestamp() {
+    let output = idgen()
+        .args(["-t", "uuid4", "--at", "1700000000"])
         .output()
         .expect("Failed to execute command");
 
@@ -204,13 +1375,13 @@ fn test_exit_code_usage_error_uuid_v3_missing_name() {
     assert_eq!(output.status.code(), Some(2));
 
     let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.to_lowercase().contains("name"));
+    assert!(stderr.contains("does not embed a timestamp"));
 }
 
 #[test]
-fn test_exit_code_usage_error_uuid_v3_missing_both() {
-    let output = Command::new(idgen_bin())
-        .args(["-t", "uuid3"])
+fn test_exit_code_usage_error_from_after_to() {
+    let output = idgen()
+        .args(["-t", "ulid", "--from", "1700000000", "--to", "1600000000"])
         .output()
         .expect("Failed to execute command");
 
@@ -219,9 +1390,9 @@ fn test_exit_code_usage_error_uuid_v3_missing_both() {
 }
 
 #[test]
-fn test_exit_code_usage_error_uuid_v5_missing_namespace() {
-    let output = Command::new(idgen_bin())
-        .args(["-t", "uuid5", "--name", "test"])
+fn test_exit_code_usage_error_monotonic_not_ulid() {
+    let output = idgen()
+        .args(["-t", "uuid7", "--monotonic"])
         .output()
         .expect("Failed to execute command");
 
@@ -230,9 +1401,9 @@ fn test_exit_code_usage_error_uuid_v5_missing_namespace() {
 }
 
 #[test]
-fn test_exit_code_usage_error_uuid_v5_missing_name() {
-    let output = Command::new(idgen_bin())
-        .args(["-t", "uuid5", "--namespace", "DNS"])
+fn test_exit_code_usage_error_uuid_v1_invalid_node() {
+    let output = idgen()
+        .args(["-t", "uuid1", "--node", "00:1a:2b"])
         .output()
         .expect("Failed to execute command");
 
@@ -241,9 +1412,9 @@ fn test_exit_code_usage_error_uuid_v5_missing_name() {
 }
 
 #[test]
-fn test_exit_code_usage_error_invalid_namespace_format() {
-    let output = Command::new(idgen_bin())
-        .args(["-t", "uuid3", "--namespace", "x", "--name", "test"])
+fn test_exit_code_usage_error_names_from_wrong_type() {
+    let output = idgen()
+        .args(["-t", "uuid4", "--names-from", "-"])
         .output()
         .expect("Failed to execute command");
 
@@ -253,7 +1424,7 @@ fn test_exit_code_usage_error_invalid_namespace_format() {
 
 #[test]
 fn test_exit_code_usage_error_count_zero() {
-    let output = Command::new(idgen_bin())
+    let output = idgen()
         .args(["-c", "0"])
         .output()
         .expect("Failed to execute command");
@@ -265,13 +1436,177 @@ fn test_exit_code_usage_error_count_zero() {
     assert!(stderr.contains("Count must be at least 1"));
 }
 
+#[test]
+fn test_exit_code_usage_error_seed_without_time() {
+    let output = idgen()
+        .args(["-t", "ulid", "--seed", "42"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--at"));
+}
+
+#[test]
+fn test_exit_code_usage_error_seed_cuid1() {
+    let output = idgen()
+        .args(["-t", "cuid1", "--seed", "42"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_exit_code_usage_error_template_nanoid_extra_options() {
+    let output = idgen()
+        .args(["--template", "{nanoid:4:01:x}"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("expected LENGTH, ALPHABET or LENGTH:ALPHABET"));
+}
+
+#[test]
+fn test_exit_code_usage_error_template_unknown_placeholder() {
+    let output = idgen()
+        .args(["--template", "ord-{bogus}"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("{bogus}"));
+}
+
+#[test]
+fn test_exit_code_usage_error_threads_with_seed() {
+    let output = idgen()
+        .args(["-c", "5", "--threads", "2", "--seed", "42"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("--threads cannot be combined with --seed"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_exit_code_usage_error_threads_with_sorted_spread() {
+    let output = idgen()
+        .args([
+            "-t",
+            "ulid",
+            "-c",
+            "5",
+            "--threads",
+            "2",
+            "--from",
+            "2020-01-01T00:00:00Z",
+            "--to",
+            "2024-01-01T00:00:00Z",
+            "--spread",
+            "random",
+            "--sorted",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--threads cannot be combined with --spread random --sorted"));
+}
+
+#[test]
+fn test_exit_code_usage_error_monotonic_unsorted_spread() {
+    let args = [
+        "-t",
+        "ulid",
+        "--monotonic",
+        "-c",
+        "3",
+        "--from",
+        "2024-01-01T00:00:00Z",
+        "--to",
+        "2024-06-01T00:00:00Z",
+        "--spread",
+        "random",
+    ];
+    let output = idgen()
+        .args(args)
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--monotonic with --spread random needs --sorted"));
+
+    let output = idgen()
+        .args(args)
+        .arg("--sorted")
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_usage_error_stream_with_count() {
+    let output = idgen()
+        .args(["--stream", "-c", "5"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_exit_code_usage_error_invalid_rate() {
+    for rate in ["0/s", "fast", "10/d"] {
+        let output = idgen()
+            .args(["--stream", "--rate", rate])
+            .output()
+            .expect("Failed to execute command");
+
+        assert_eq!(output.status.code(), Some(2), "{}", rate);
+    }
+}
+
+#[test]
+fn test_exit_code_usage_error_append_json() {
+    let path = std::env::temp_dir().join(format!("idgen-append-{}.json", std::process::id()));
+    let output = idgen()
+        .args(["--json", "--append", "-o"])
+        .arg(&path)
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_exit_code_usage_error_columns_plain() {
+    let output = idgen()
+        .args(["--columns", "type"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
 // ============================================
 // Multiple IDs - Success
 // ============================================
 
 #[test]
 fn test_exit_code_success_multiple_ids() {
-    let output = Command::new(idgen_bin())
+    let output = idgen()
         .args(["-c", "5"])
         .output()
         .expect("Failed to execute command");
@@ -290,7 +1625,7 @@ fn test_exit_code_success_multiple_ids() {
 
 #[test]
 fn test_exit_code_success_with_prefix() {
-    let output = Command::new(idgen_bin())
+    let output = idgen()
         .args(["-p", "test-"])
         .output()
         .expect("Failed to execute command");
@@ -304,7 +1639,7 @@ fn test_exit_code_success_with_prefix() {
 
 #[test]
 fn test_exit_code_success_with_suffix() {
-    let output = Command::new(idgen_bin())
+    let output = idgen()
         .args(["-s", ".log"])
         .output()
         .expect("Failed to execute command");
@@ -322,7 +1657,7 @@ fn test_exit_code_success_with_suffix() {
 
 #[test]
 fn test_exit_code_success_completions_bash() {
-    let output = Command::new(idgen_bin())
+    let output = idgen()
         .args(["completions", "bash"])
         .output()
         .expect("Failed to execute command");
@@ -336,7 +1671,7 @@ fn test_exit_code_success_completions_bash() {
 
 #[test]
 fn test_exit_code_success_completions_zsh() {
-    let output = Command::new(idgen_bin())
+    let output = idgen()
         .args(["completions", "zsh"])
         .output()
         .expect("Failed to execute command");
@@ -347,7 +1682,7 @@ fn test_exit_code_success_completions_zsh() {
 
 #[test]
 fn test_exit_code_success_completions_fish() {
-    let output = Command::new(idgen_bin())
+    let output = idgen()
         .args(["completions", "fish"])
         .output()
         .expect("Failed to execute command");
@@ -358,7 +1693,7 @@ fn test_exit_code_success_completions_fish() {
 
 #[test]
 fn test_exit_code_success_completions_powershell() {
-    let output = Command::new(idgen_bin())
+    let output = idgen()
         .args(["completions", "powershell"])
         .output()
         .expect("Failed to execute command");
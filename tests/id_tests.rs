@@ -1,4 +1,8 @@
-use idgen_cli::id::{new_id, CuidVersion, IDError, IDFormat, UuidVersion};
+use idgen_cli::id::{
+    new_id, new_id_at, uuid_v1_to_v6, uuid_v6_to_v1, CuidVersion, IDError, IDFormat, NanoAlphabet,
+    UuidV1Fields, UuidV8Fields, UuidVersion,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 // ============================================
 // UUID v4 Tests
@@ -63,13 +67,13 @@ fn test_objectid() {
 #[test]
 fn test_nanoid() {
     let len = 10;
-    let id = new_id(&IDFormat::NanoID, Some(len), None, None).unwrap();
+    let id = new_id(&IDFormat::NanoID(None), Some(len), None, None).unwrap();
     assert_eq!(id.len(), len);
 }
 
 #[test]
 fn test_nanoid_default_length() {
-    let id = new_id(&IDFormat::NanoID, None, None, None).unwrap();
+    let id = new_id(&IDFormat::NanoID(None), None, None, None).unwrap();
     assert_eq!(id.len(), 21);
 }
 
@@ -109,31 +113,349 @@ fn test_ulid() {
     assert_eq!(id, parsed.to_string())
 }
 
+#[test]
+fn test_ksuid() {
+    let id = new_id(&IDFormat::Ksuid, None, None, None).unwrap();
+    assert_eq!(id.len(), 27);
+    assert!(id.chars().all(|c| c.is_ascii_alphanumeric()));
+}
+
+#[test]
+fn test_ksuid_uniqueness() {
+    let id1 = new_id(&IDFormat::Ksuid, None, None, None).unwrap();
+    let id2 = new_id(&IDFormat::Ksuid, None, None, None).unwrap();
+    assert_ne!(id1, id2);
+}
+
+#[test]
+fn test_typeid() {
+    let id = new_id(&IDFormat::TypeId("user".to_string()), None, None, None).unwrap();
+    let (prefix, suffix) = id.split_once('_').unwrap();
+    assert_eq!(prefix, "user");
+    assert_eq!(suffix.len(), 26);
+}
+
+#[test]
+fn test_typeid_invalid_prefix() {
+    let result = new_id(&IDFormat::TypeId("User".to_string()), None, None, None);
+    assert!(matches!(result, Err(IDError::InvalidTypeId(_))));
+}
+
+#[test]
+fn test_xid() {
+    let id = new_id(&IDFormat::Xid, None, None, None).unwrap();
+    assert_eq!(id.len(), 20);
+    assert!(id.chars().all(|c| matches!(c, '0'..='9' | 'a'..='v')));
+}
+
+#[test]
+fn test_xid_uniqueness() {
+    let id1 = new_id(&IDFormat::Xid, None, None, None).unwrap();
+    let id2 = new_id(&IDFormat::Xid, None, None, None).unwrap();
+    assert_ne!(id1, id2);
+}
+
 // ============================================
 // UUID v1 Tests (Time-based)
 // ============================================
 
 #[test]
 fn test_uuid_v1_simple() {
-    let id = new_id(&IDFormat::Simple(UuidVersion::V1), None, None, None).unwrap();
+    let id = new_id(
+        &IDFormat::Simple(UuidVersion::V1(UuidV1Fields::default())),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
     assert_eq!(id.len(), 32);
     assert!(!id.contains('-'));
 }
 
 #[test]
 fn test_uuid_v1_hyphenated() {
-    let id = new_id(&IDFormat::Hyphenated(UuidVersion::V1), None, None, None).unwrap();
+    let id = new_id(
+        &IDFormat::Hyphenated(UuidVersion::V1(UuidV1Fields::default())),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
     assert_eq!(id.len(), 36);
     assert_eq!(id.matches('-').count(), 4);
 }
 
 #[test]
 fn test_uuid_v1_urn() {
-    let id = new_id(&IDFormat::URN(UuidVersion::V1), None, None, None).unwrap();
+    let id = new_id(
+        &IDFormat::URN(UuidVersion::V1(UuidV1Fields::default())),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
     assert!(id.starts_with("urn:uuid:"));
     assert_eq!(id.len(), 45);
 }
 
+#[test]
+fn test_uuid_v1_default_node_is_random_multicast() {
+    let format = IDFormat::Hyphenated(UuidVersion::V1(UuidV1Fields::default()));
+    let id1 = uuid::Uuid::parse_str(&new_id(&format, None, None, None).unwrap()).unwrap();
+    let id2 = uuid::Uuid::parse_str(&new_id(&format, None, None, None).unwrap()).unwrap();
+    let node = id1.get_node_id().unwrap();
+    assert_ne!(node, [1, 2, 3, 4, 5, 6]);
+    assert_eq!(node[0] & 0x01, 0x01);
+    // The random node is chosen once per process
+    assert_eq!(id2.get_node_id().unwrap(), node);
+}
+
+#[test]
+fn test_uuid_v1_custom_node_and_clock_seq() {
+    let node = UuidV1Fields::parse_node("00:1a:2b:3c:4d:5e").unwrap();
+    let fields = UuidV1Fields::new(Some(node), Some(42)).unwrap();
+    let format = IDFormat::Hyphenated(UuidVersion::V1(fields));
+
+    let ids: Vec<String> = (0..1_000)
+        .map(|_| new_id(&format, None, None, None).unwrap())
+        .collect();
+    for id in &ids {
+        assert!(id.ends_with("-802a-001a2b3c4d5e"));
+    }
+    // A fixed clock sequence still yields unique IDs
+    let unique: std::collections::HashSet<_> = ids.iter().collect();
+    assert_eq!(unique.len(), ids.len());
+}
+
+#[test]
+fn test_uuid_v1_parse_node_formats() {
+    let expected = [0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e];
+    assert_eq!(UuidV1Fields::parse_node("001a2b3c4d5e").unwrap(), expected);
+    assert_eq!(
+        UuidV1Fields::parse_node("00-1A-2B-3C-4D-5E").unwrap(),
+        expected
+    );
+    for node in [
+        "00:1a:2b",
+        "0:0:1:a:2:b:3:c:4:d:5:e",
+        "001a:2b3c:4d5e",
+        "00:1a-2b:3c:4d:5e",
+        "+01a2b3c4d5e",
+    ] {
+        assert!(
+            matches!(UuidV1Fields::parse_node(node), Err(IDError::InvalidNode(_))),
+            "{}",
+            node
+        );
+    }
+}
+
+#[test]
+fn test_uuid_v1_clock_seq_too_wide() {
+    let result = UuidV1Fields::new(None, Some(0x4000));
+    assert!(matches!(result, Err(IDError::InvalidNode(_))));
+}
+
+#[test]
+fn test_uuid_v1_machine_node_is_stable() {
+    // Only meaningful where a machine ID exists
+    if let Ok(node) = UuidV1Fields::machine_node() {
+        assert_eq!(UuidV1Fields::machine_node().unwrap(), node);
+        assert_eq!(node[0] & 0x01, 0x01);
+    }
+}
+
+// ============================================
+// UUID v6 Tests (Reordered time-based)
+// ============================================
+
+#[test]
+fn test_uuid_v6_formats() {
+    let simple = new_id(
+        &IDFormat::Simple(UuidVersion::V6(UuidV1Fields::default())),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(simple.len(), 32);
+    let hyphenated = new_id(
+        &IDFormat::Hyphenated(UuidVersion::V6(UuidV1Fields::default())),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(hyphenated.len(), 36);
+    let urn = new_id(
+        &IDFormat::URN(UuidVersion::V6(UuidV1Fields::default())),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(urn.starts_with("urn:uuid:"));
+}
+
+#[test]
+fn test_uuid_v6_version_nibble() {
+    let id = new_id(
+        &IDFormat::Hyphenated(UuidVersion::V6(UuidV1Fields::default())),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    let parsed = uuid::Uuid::parse_str(&id).unwrap();
+    assert_eq!(parsed.get_version_num(), 6);
+}
+
+#[test]
+fn test_uuid_v1_to_v6_rfc_vector() {
+    // RFC 9562 Appendix A test vectors for the same instant
+    let v1 = uuid::Uuid::parse_str("c232ab00-9414-11ec-b3c8-9f6bdeced846").unwrap();
+    let v6 = uuid_v1_to_v6(&v1).unwrap();
+    assert_eq!(v6.to_string(), "1ec9414c-232a-6b00-b3c8-9f6bdeced846");
+}
+
+#[test]
+fn test_uuid_v6_to_v1_rfc_vector() {
+    let v6 = uuid::Uuid::parse_str("1ec9414c-232a-6b00-b3c8-9f6bdeced846").unwrap();
+    let v1 = uuid_v6_to_v1(&v6).unwrap();
+    assert_eq!(v1.to_string(), "c232ab00-9414-11ec-b3c8-9f6bdeced846");
+}
+
+#[test]
+fn test_uuid_v1_v6_round_trip() {
+    let id = new_id(
+        &IDFormat::Hyphenated(UuidVersion::V1(UuidV1Fields::default())),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    let v1 = uuid::Uuid::parse_str(&id).unwrap();
+    let v6 = uuid_v1_to_v6(&v1).unwrap();
+    assert_eq!(v6.get_timestamp(), v1.get_timestamp());
+    assert_eq!(uuid_v6_to_v1(&v6).unwrap(), v1);
+}
+
+#[test]
+fn test_uuid_v1_to_v6_rejects_other_versions() {
+    let v4 = uuid::Uuid::parse_str("550e8400-e29b-44d4-a716-446655440000").unwrap();
+    assert!(matches!(uuid_v1_to_v6(&v4), Err(IDError::InvalidUuid(_))));
+    assert!(matches!(uuid_v6_to_v1(&v4), Err(IDError::InvalidUuid(_))));
+}
+
+// ============================================
+// UUID v7 Tests (Unix time-ordered)
+// ============================================
+
+#[test]
+fn test_uuid_v7_formats() {
+    let simple = new_id(&IDFormat::Simple(UuidVersion::V7), None, None, None).unwrap();
+    assert_eq!(simple.len(), 32);
+    let hyphenated = new_id(&IDFormat::Hyphenated(UuidVersion::V7), None, None, None).unwrap();
+    assert_eq!(hyphenated.len(), 36);
+    let urn = new_id(&IDFormat::URN(UuidVersion::V7), None, None, None).unwrap();
+    assert!(urn.starts_with("urn:uuid:"));
+}
+
+#[test]
+fn test_uuid_v7_version_nibble() {
+    let id = new_id(&IDFormat::Hyphenated(UuidVersion::V7), None, None, None).unwrap();
+    let parsed = uuid::Uuid::parse_str(&id).unwrap();
+    assert_eq!(parsed.get_version_num(), 7);
+}
+
+#[test]
+fn test_uuid_v7_time_ordered() {
+    let id1 = new_id(&IDFormat::Simple(UuidVersion::V7), None, None, None).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    let id2 = new_id(&IDFormat::Simple(UuidVersion::V7), None, None, None).unwrap();
+    assert!(id1 < id2);
+}
+
+// ============================================
+// UUID v8 Tests (Custom payload)
+// ============================================
+
+#[test]
+fn test_uuid_v8_random_fields() {
+    let version = UuidVersion::V8(UuidV8Fields::default());
+    let id = new_id(&IDFormat::Hyphenated(version), None, None, None).unwrap();
+    let parsed = uuid::Uuid::parse_str(&id).unwrap();
+    assert_eq!(parsed.get_version_num(), 8);
+    assert_eq!(parsed.get_variant(), uuid::Variant::RFC4122);
+}
+
+#[test]
+fn test_uuid_v8_custom_fields() {
+    let fields = UuidV8Fields::new(Some(0x2a), Some(0x7), Some(0x1234)).unwrap();
+    let id = new_id(
+        &IDFormat::Hyphenated(UuidVersion::V8(fields)),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(id, "00000000-002a-8007-8000-000000001234");
+}
+
+#[test]
+fn test_uuid_v8_partial_fields_keep_tag() {
+    let fields = UuidV8Fields::new(Some(0xabcdef), None, None).unwrap();
+    let id1 = new_id(&IDFormat::Simple(UuidVersion::V8(fields)), None, None, None).unwrap();
+    let id2 = new_id(&IDFormat::Simple(UuidVersion::V8(fields)), None, None, None).unwrap();
+    assert!(id1.starts_with("000000abcdef8"));
+    assert_ne!(id1, id2);
+}
+
+#[test]
+fn test_uuid_v8_hex_payload_sets_version_and_variant() {
+    let fields = UuidV8Fields::from_hex("ffffffff-ffff-ffff-ffff-ffffffffffff").unwrap();
+    let id = new_id(
+        &IDFormat::Hyphenated(UuidVersion::V8(fields)),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(id, "ffffffff-ffff-8fff-bfff-ffffffffffff");
+}
+
+#[test]
+fn test_uuid_v8_invalid_payload() {
+    assert!(matches!(
+        UuidV8Fields::from_hex("abc"),
+        Err(IDError::InvalidPayload(_))
+    ));
+    assert!(matches!(
+        UuidV8Fields::from_hex("zz112233445566778899aabbccddeeff"),
+        Err(IDError::InvalidPayload(_))
+    ));
+    assert!(matches!(
+        UuidV8Fields::from_hex("+0112233445566778899aabbccddeeff"),
+        Err(IDError::InvalidPayload(_))
+    ));
+}
+
+#[test]
+fn test_uuid_v8_field_too_wide() {
+    assert!(matches!(
+        UuidV8Fields::new(Some(1 << 48), None, None),
+        Err(IDError::InvalidPayload(_))
+    ));
+    assert!(matches!(
+        UuidV8Fields::new(None, Some(0x1000), None),
+        Err(IDError::InvalidPayload(_))
+    ));
+    assert!(matches!(
+        UuidV8Fields::new(None, None, Some(1 << 62)),
+        Err(IDError::InvalidPayload(_))
+    ));
+}
+
 // ============================================
 // UUID v5 Error Cases
 // ============================================
@@ -241,25 +563,76 @@ fn test_uuid_v5_deterministic() {
 
 #[test]
 fn test_nanoid_minimum_length() {
-    let id = new_id(&IDFormat::NanoID, Some(1), None, None).unwrap();
+    let id = new_id(&IDFormat::NanoID(None), Some(1), None, None).unwrap();
     assert_eq!(id.len(), 1);
 }
 
 #[test]
 fn test_nanoid_large_length() {
-    let id = new_id(&IDFormat::NanoID, Some(100), None, None).unwrap();
+    let id = new_id(&IDFormat::NanoID(None), Some(100), None, None).unwrap();
     assert_eq!(id.len(), 100);
 }
 
 #[test]
 fn test_nanoid_url_safe_chars() {
-    let id = new_id(&IDFormat::NanoID, Some(50), None, None).unwrap();
+    let id = new_id(&IDFormat::NanoID(None), Some(50), None, None).unwrap();
     // NanoID uses URL-safe alphabet: A-Za-z0-9_-
     assert!(id
         .chars()
         .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'));
 }
 
+// ============================================
+// NanoID Custom Alphabet Tests
+// ============================================
+
+#[test]
+fn test_nanoid_custom_alphabet() {
+    let alphabet = NanoAlphabet::new("abc").unwrap();
+    let id = new_id(&IDFormat::NanoID(Some(alphabet)), Some(40), None, None).unwrap();
+    assert_eq!(id.len(), 40);
+    assert!(id.chars().all(|c| "abc".contains(c)));
+}
+
+#[test]
+fn test_nanoid_preset_numbers() {
+    let alphabet = NanoAlphabet::preset("numbers").unwrap();
+    let id = new_id(&IDFormat::NanoID(Some(alphabet)), Some(6), None, None).unwrap();
+    assert_eq!(id.len(), 6);
+    assert!(id.chars().all(|c| c.is_ascii_digit()));
+}
+
+#[test]
+fn test_nanoid_preset_nolookalikes() {
+    let alphabet = NanoAlphabet::preset("NoLookalikes").unwrap();
+    let id = new_id(&IDFormat::NanoID(Some(alphabet)), Some(100), None, None).unwrap();
+    assert!(!id.contains(['0', 'O', '1', 'l', 'I']));
+}
+
+#[test]
+fn test_nanoid_unknown_preset() {
+    assert!(NanoAlphabet::preset("emoji").is_none());
+}
+
+#[test]
+fn test_nanoid_alphabet_too_short() {
+    let result = NanoAlphabet::new("x");
+    assert!(matches!(result, Err(IDError::InvalidAlphabet(_))));
+}
+
+#[test]
+fn test_nanoid_alphabet_duplicate_chars() {
+    let err = NanoAlphabet::new("abca").unwrap_err();
+    assert!(matches!(err, IDError::InvalidAlphabet(_)));
+    assert!(err.to_string().contains("'a'"));
+}
+
+#[test]
+fn test_nanoid_alphabet_non_ascii() {
+    let result = NanoAlphabet::new("aé");
+    assert!(matches!(result, Err(IDError::InvalidAlphabet(_))));
+}
+
 // ============================================
 // Uniqueness Tests
 // ============================================
@@ -273,8 +646,8 @@ fn test_uuid_v4_uniqueness() {
 
 #[test]
 fn test_nanoid_uniqueness() {
-    let id1 = new_id(&IDFormat::NanoID, None, None, None).unwrap();
-    let id2 = new_id(&IDFormat::NanoID, None, None, None).unwrap();
+    let id1 = new_id(&IDFormat::NanoID(None), None, None, None).unwrap();
+    let id2 = new_id(&IDFormat::NanoID(None), None, None, None).unwrap();
     assert_ne!(id1, id2);
 }
 
@@ -327,3 +700,124 @@ fn test_ulid_uppercase() {
         .chars()
         .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()));
 }
+
+// ============================================
+// Explicit Timestamp Tests
+// ============================================
+
+fn at_millis(millis: u64) -> Option<SystemTime> {
+    Some(UNIX_EPOCH + Duration::from_millis(millis))
+}
+
+#[test]
+fn test_ulid_at() {
+    let id = new_id_at(
+        &IDFormat::Ulid,
+        None,
+        None,
+        None,
+        at_millis(1_582_979_696_789),
+    )
+    .unwrap();
+    let ulid = ulid::Ulid::from_string(&id).unwrap();
+    assert_eq!(ulid.timestamp_ms(), 1_582_979_696_789);
+}
+
+#[test]
+fn test_uuid_v7_at() {
+    let format = IDFormat::Hyphenated(UuidVersion::V7);
+    let id = new_id_at(&format, None, None, None, at_millis(1_582_979_696_789)).unwrap();
+    let uuid = uuid::Uuid::parse_str(&id).unwrap();
+    let (seconds, nanos) = uuid.get_timestamp().unwrap().to_unix();
+    assert_eq!((seconds, nanos), (1_582_979_696, 789_000_000));
+}
+
+#[test]
+fn test_uuid_v1_at_same_instant_unique() {
+    let format = IDFormat::Hyphenated(UuidVersion::V1(UuidV1Fields::default()));
+    let id1 = new_id_at(&format, None, None, None, at_millis(1_700_000_000_000)).unwrap();
+    let id2 = new_id_at(&format, None, None, None, at_millis(1_700_000_000_000)).unwrap();
+    assert_ne!(id1, id2);
+    let uuid = uuid::Uuid::parse_str(&id1).unwrap();
+    assert_eq!(uuid.get_timestamp().unwrap().to_unix().0, 1_700_000_000);
+}
+
+#[test]
+fn test_uuid_v6_at_clock_seq_exhausted() {
+    let format = IDFormat::Hyphenated(UuidVersion::V6(UuidV1Fields::default()));
+    let ids: std::collections::HashSet<String> = (0..16_384)
+        .map(|_| new_id_at(&format, None, None, None, at_millis(1_400_000_000_000)).unwrap())
+        .collect();
+    assert_eq!(ids.len(), 16_384);
+    let result = new_id_at(&format, None, None, None, at_millis(1_400_000_000_000));
+    assert!(matches!(result, Err(IDError::InvalidTimestamp(_))));
+}
+
+#[test]
+fn test_uuid_v1_at_with_clock_seq_unique() {
+    let fields = UuidV1Fields::new(None, Some(5)).unwrap();
+    let format = IDFormat::Hyphenated(UuidVersion::V1(fields));
+    // Repeats of one instant, and an instant that comes back after another
+    let ids: Vec<String> = [1_500_000_000_000u64; 4]
+        .into_iter()
+        .chain([1_500_000_000_001, 1_500_000_000_000])
+        .map(|millis| new_id_at(&format, None, None, None, at_millis(millis)).unwrap())
+        .collect();
+    let unique: std::collections::HashSet<_> = ids.iter().collect();
+    assert_eq!(unique.len(), ids.len());
+    let seconds = uuid::Uuid::parse_str(&ids[0])
+        .unwrap()
+        .get_timestamp()
+        .unwrap()
+        .to_unix();
+    assert_eq!(seconds, (1_500_000_000, 0));
+}
+
+#[test]
+fn test_objectid_at() {
+    let id = new_id_at(
+        &IDFormat::OID,
+        None,
+        None,
+        None,
+        at_millis(1_700_000_000_000),
+    )
+    .unwrap();
+    assert_eq!(&id[..8], format!("{:08x}", 1_700_000_000u32));
+}
+
+#[test]
+fn test_at_rejected_without_timestamp() {
+    let format = IDFormat::Hyphenated(UuidVersion::V4);
+    let result = new_id_at(&format, None, None, None, at_millis(0));
+    assert!(matches!(result, Err(IDError::InvalidTimestamp(_))));
+
+    let result = new_id_at(&IDFormat::NanoID(None), None, None, None, at_millis(0));
+    assert!(matches!(result, Err(IDError::InvalidTimestamp(_))));
+}
+
+#[test]
+fn test_at_before_unix_epoch() {
+    let at = Some(UNIX_EPOCH - Duration::from_secs(1));
+    let result = new_id_at(&IDFormat::Ulid, None, None, None, at);
+    assert!(matches!(result, Err(IDError::InvalidTimestamp(_))));
+}
+
+#[test]
+fn test_at_past_48_bit_millis() {
+    let last = at_millis((1 << 48) - 1);
+    let past = at_millis(1 << 48);
+    for format in [
+        IDFormat::Ulid,
+        IDFormat::Hyphenated(UuidVersion::V7),
+        IDFormat::TypeId("user".to_string()),
+    ] {
+        assert!(new_id_at(&format, None, None, None, last).is_ok());
+        let result = new_id_at(&format, None, None, None, past);
+        assert!(
+            matches!(result, Err(IDError::InvalidTimestamp(_))),
+            "{:?}",
+            format
+        );
+    }
+}
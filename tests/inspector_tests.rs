@@ -1,4 +1,5 @@
-use idgen_cli::inspector::inspect_id;
+use idgen_cli::inspector::{inspect_id, inspect_snowflake};
+use idgen_cli::snowflake::SnowflakeConfig;
 
 // ============================================
 // UUID Detection Tests
@@ -22,6 +23,84 @@ fn test_inspect_uuid_v1() {
     assert_eq!(result.version, Some("Mac".to_string()));
 }
 
+#[test]
+fn test_inspect_uuid_v1_timestamp() {
+    let result = inspect_id("f47ac10b-58cc-11e4-8b58-0800200c9a66");
+    assert!(result.timestamp.unwrap().starts_with("2014-10-"));
+}
+
+#[test]
+fn test_inspect_uuid_v6() {
+    let result = inspect_id("1ec9414c-232a-6b00-b3c8-9f6bdeced846");
+    assert!(result.valid);
+    assert_eq!(result.version, Some("SortMac".to_string()));
+    assert_eq!(
+        result.timestamp,
+        Some("2022-02-22T19:22:22+00:00".to_string())
+    );
+}
+
+#[test]
+fn test_inspect_uuid_v7() {
+    // 0x017F22E279B0 ms = 2022-02-22T19:22:22Z (RFC 9562 test vector)
+    let result = inspect_id("017f22e2-79b0-7cc3-98c4-dc0c0c07398f");
+    assert!(result.valid);
+    assert_eq!(result.id_type, "UUID");
+    assert_eq!(result.version, Some("SortRand".to_string()));
+    assert_eq!(
+        result.timestamp,
+        Some("2022-02-22T19:22:22+00:00".to_string())
+    );
+}
+
+#[test]
+fn test_inspect_uuid_v8_custom_fields() {
+    let result = inspect_id("00000000-002a-8007-8000-000000001234");
+    assert!(result.valid);
+    assert_eq!(result.version, Some("Custom".to_string()));
+    assert!(result.timestamp.is_none());
+    assert_eq!(
+        result.components,
+        vec![
+            ("custom_a".to_string(), "0x00000000002a".to_string()),
+            ("custom_b".to_string(), "0x007".to_string()),
+            ("custom_c".to_string(), "0x0000000000001234".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_inspect_uuid_v1_node_and_clock_seq() {
+    let result = inspect_id("c232ab00-9414-11ec-b3c8-9f6bdeced846");
+    assert_eq!(result.version, Some("Mac".to_string()));
+    assert_eq!(
+        result.components,
+        vec![
+            ("node".to_string(), "9f:6b:de:ce:d8:46 (random)".to_string()),
+            ("clock_seq".to_string(), "13256".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_inspect_uuid_v6_mac_node() {
+    let result = inspect_id("1ec9414c-232a-6b00-802a-001a2b3c4d5e");
+    assert_eq!(result.version, Some("SortMac".to_string()));
+    assert_eq!(
+        result.components,
+        vec![
+            ("node".to_string(), "00:1a:2b:3c:4d:5e (MAC)".to_string()),
+            ("clock_seq".to_string(), "42".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_inspect_uuid_v4_has_no_timestamp() {
+    let result = inspect_id("550e8400-e29b-44d4-a716-446655440000");
+    assert!(result.timestamp.is_none());
+}
+
 #[test]
 fn test_inspect_uuid_simple_format() {
     // UUID without hyphens
@@ -84,6 +163,105 @@ fn test_inspect_objectid_uppercase() {
     assert_eq!(result.id_type, "ObjectId");
 }
 
+// ============================================
+// TypeID Detection Tests
+// ============================================
+
+#[test]
+fn test_inspect_typeid() {
+    let result = inspect_id("prefix_01h455vb4pex5vsknk084sn02q");
+    assert!(result.valid);
+    assert_eq!(result.id_type, "TypeID");
+    assert_eq!(result.version, Some("SortRand".to_string()));
+    assert_eq!(
+        result.timestamp,
+        Some("2023-06-30T03:34:18.518+00:00".to_string())
+    );
+    assert_eq!(
+        result.components,
+        vec![
+            ("prefix".to_string(), "prefix".to_string()),
+            (
+                "suffix".to_string(),
+                "01h455vb4pex5vsknk084sn02q".to_string()
+            ),
+            (
+                "uuid".to_string(),
+                "01890a5d-ac96-774b-bcce-b302099a8057".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_inspect_typeid_starting_with_c_is_not_cuid() {
+    let result = inspect_id("customer_01h455vb4pex5vsknk084sn02q");
+    assert_eq!(result.id_type, "TypeID");
+}
+
+#[test]
+fn test_inspect_bare_typeid_suffix_is_ulid() {
+    let result = inspect_id("01h455vb4pex5vsknk084sn02q");
+    assert_eq!(result.id_type, "ULID");
+}
+
+// ============================================
+// xid Detection Tests
+// ============================================
+
+#[test]
+fn test_inspect_xid() {
+    let result = inspect_id("9m4e2mr0ui3e8a215n4g");
+    assert!(result.valid);
+    assert_eq!(result.id_type, "xid");
+    assert_eq!(
+        result.timestamp,
+        Some("2011-03-22T17:50:19+00:00".to_string())
+    );
+    assert_eq!(
+        result.components,
+        vec![
+            ("machine_id".to_string(), "60f486".to_string()),
+            ("pid".to_string(), "58408".to_string()),
+            ("counter".to_string(), "4271561".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_inspect_xid_non_canonical_is_unknown() {
+    let result = inspect_id("9m4e2mr0ui3e8a215n4h");
+    assert!(!result.valid);
+}
+
+// ============================================
+// KSUID Detection Tests
+// ============================================
+
+#[test]
+fn test_inspect_ksuid() {
+    let result = inspect_id("0ujtsYcgvSTl8PAuAdqWYSMnLOv");
+    assert!(result.valid);
+    assert_eq!(result.id_type, "KSUID");
+    assert_eq!(
+        result.timestamp,
+        Some("2017-10-10T04:00:47+00:00".to_string())
+    );
+    assert_eq!(
+        result.components,
+        vec![(
+            "payload".to_string(),
+            "b5a1cd34b5f99d1154fb6853345c9735".to_string()
+        )]
+    );
+}
+
+#[test]
+fn test_inspect_ksuid_overflow_is_not_ksuid() {
+    let result = inspect_id("aWgEPTl1tmebfsQzFP4bxwgy80W");
+    assert_ne!(result.id_type, "KSUID");
+}
+
 // ============================================
 // CUID Detection Tests
 // ============================================
@@ -237,3 +415,40 @@ fn test_inspect_numeric_only() {
     assert!(!result.valid);
     assert_eq!(result.id_type, "Unknown");
 }
+
+// ============================================
+// Snowflake Decoding Tests
+// ============================================
+
+#[test]
+fn test_inspect_snowflake_twitter() {
+    let result = inspect_snowflake("1541815603606036480", &SnowflakeConfig::twitter());
+    assert!(result.valid);
+    assert_eq!(result.id_type, "Snowflake");
+    assert_eq!(
+        result.timestamp,
+        Some("2022-06-28T16:07:40.105+00:00".to_string())
+    );
+    assert_eq!(
+        result.components,
+        vec![
+            ("datacenter_id".to_string(), "11".to_string()),
+            ("worker_id".to_string(), "26".to_string()),
+            ("sequence".to_string(), "0".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_inspect_snowflake_instagram_has_no_datacenter() {
+    let result = inspect_snowflake("1234567890123456789", &SnowflakeConfig::instagram());
+    assert!(result.valid);
+    assert!(!result.components.iter().any(|(k, _)| k == "datacenter_id"));
+}
+
+#[test]
+fn test_inspect_snowflake_not_a_number() {
+    let result = inspect_snowflake("abc", &SnowflakeConfig::twitter());
+    assert!(!result.valid);
+    assert_eq!(result.id_type, "Unknown");
+}
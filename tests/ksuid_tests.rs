@@ -0,0 +1,76 @@
+use idgen_cli::ksuid::{Ksuid, KSUID_LENGTH};
+
+// ============================================
+// Encoding Tests
+// ============================================
+
+#[test]
+fn test_ksuid_reference_value() {
+    // Example from the segmentio/ksuid README
+    let ksuid = Ksuid::parse("0ujtsYcgvSTl8PAuAdqWYSMnLOv").unwrap();
+    assert_eq!(ksuid.unix_timestamp(), 1507608047);
+    assert_eq!(
+        ksuid.payload,
+        [
+            0xb5, 0xa1, 0xcd, 0x34, 0xb5, 0xf9, 0x9d, 0x11, 0x54, 0xfb, 0x68, 0x53, 0x34, 0x5c,
+            0x97, 0x35
+        ]
+    );
+    assert_eq!(ksuid.to_string(), "0ujtsYcgvSTl8PAuAdqWYSMnLOv");
+}
+
+#[test]
+fn test_ksuid_min_and_max() {
+    let min = Ksuid {
+        timestamp: 0,
+        payload: [0; 16],
+    };
+    assert_eq!(min.to_string(), "000000000000000000000000000");
+
+    let max = Ksuid {
+        timestamp: u32::MAX,
+        payload: [0xff; 16],
+    };
+    assert_eq!(max.to_string(), "aWgEPTl1tmebfsQzFP4bxwgy80V");
+}
+
+#[test]
+fn test_ksuid_round_trip() {
+    let ksuid = Ksuid::new();
+    let s = ksuid.to_string();
+    assert_eq!(s.len(), KSUID_LENGTH);
+    assert_eq!(Ksuid::parse(&s), Some(ksuid));
+}
+
+#[test]
+fn test_ksuid_from_unix() {
+    let ksuid = Ksuid::from_unix(1_700_000_000).unwrap();
+    assert_eq!(ksuid.unix_timestamp(), 1_700_000_000);
+}
+
+#[test]
+fn test_ksuid_from_unix_out_of_range() {
+    assert!(Ksuid::from_unix(1_399_999_999).is_none());
+    assert!(Ksuid::from_unix(1_400_000_000 + u32::MAX as u64 + 1).is_none());
+}
+
+// ============================================
+// Parsing Error Tests
+// ============================================
+
+#[test]
+fn test_ksuid_parse_overflow() {
+    assert_eq!(Ksuid::parse("aWgEPTl1tmebfsQzFP4bxwgy80W"), None);
+    assert_eq!(Ksuid::parse("zzzzzzzzzzzzzzzzzzzzzzzzzzz"), None);
+}
+
+#[test]
+fn test_ksuid_parse_wrong_length() {
+    assert_eq!(Ksuid::parse("0ujtsYcgvSTl8PAuAdqWYSMnLO"), None);
+    assert_eq!(Ksuid::parse("0ujtsYcgvSTl8PAuAdqWYSMnLOvv"), None);
+}
+
+#[test]
+fn test_ksuid_parse_invalid_chars() {
+    assert_eq!(Ksuid::parse("0ujtsYcgvSTl8PAuAdqWYSMnLO-"), None);
+}
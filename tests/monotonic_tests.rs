@@ -0,0 +1,51 @@
+use idgen_cli::id::{new_id, new_id_at, IDFormat};
+use idgen_cli::monotonic::{last_monotonic_ulid, next_monotonic_ulid, resume_monotonic_ulid};
+use std::time::{Duration, UNIX_EPOCH};
+use ulid::Ulid;
+
+// ============================================
+// Ordering Tests
+// ============================================
+
+#[test]
+fn test_monotonic_ulid_strictly_increasing() {
+    let mut last = next_monotonic_ulid(None).unwrap();
+    for _ in 0..10_000 {
+        let next = next_monotonic_ulid(None).unwrap();
+        assert!(next > last);
+        last = next;
+    }
+}
+
+#[test]
+fn test_monotonic_ulid_same_time_increments() {
+    let at = Some(UNIX_EPOCH + Duration::from_millis(1_700_000_000_000));
+    let first = next_monotonic_ulid(at).unwrap();
+    let second = next_monotonic_ulid(at).unwrap();
+    assert!(second > first);
+}
+
+#[test]
+fn test_new_id_monotonic_ulid_sorts_in_order() {
+    let ids: Vec<String> = (0..1_000)
+        .map(|_| new_id(&IDFormat::MonotonicUlid, None, None, None).unwrap())
+        .collect();
+    assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+// ============================================
+// Resume Tests
+// ============================================
+
+#[test]
+fn test_resume_from_later_ulid() {
+    // A ULID from far in the future, as if read from a state file
+    let previous = Ulid::from_parts(u64::MAX >> 17, 42);
+    resume_monotonic_ulid(previous);
+
+    let next =
+        Ulid::from_string(&new_id_at(&IDFormat::MonotonicUlid, None, None, None, None).unwrap())
+            .unwrap();
+    assert!(next > previous);
+    assert!(last_monotonic_ulid().unwrap() >= next);
+}
@@ -0,0 +1,258 @@
+use idgen_cli::cli::{resolve_namespace, resolve_namespace_path};
+use idgen_cli::id::IDError;
+use idgen_cli::namespace::{
+    parse_namespace_uuid, NamespaceRegistry, NamespaceSource, BUILTIN_NAMESPACES,
+};
+use uuid::Uuid;
+
+const BILLING: &str = "c3d5c1a4-5b0e-4b1a-9e0b-3f2b7f1b2a10";
+
+// ============================================
+// Lookup Tests
+// ============================================
+
+#[test]
+fn test_builtin_namespaces() {
+    let registry = NamespaceRegistry::new();
+    assert_eq!(registry.get("DNS"), Some(Uuid::NAMESPACE_DNS));
+    assert_eq!(registry.get("url"), Some(Uuid::NAMESPACE_URL));
+    assert_eq!(registry.get("billing"), None);
+    assert_eq!(registry.entries().len(), BUILTIN_NAMESPACES.len());
+}
+
+#[test]
+fn test_define_from_arg() {
+    let mut registry = NamespaceRegistry::new();
+    registry
+        .define_from_arg(&format!("billing={}", BILLING))
+        .unwrap();
+    assert_eq!(
+        registry.get("Billing"),
+        Some(Uuid::parse_str(BILLING).unwrap())
+    );
+
+    let entry = registry.entries().pop().unwrap();
+    assert_eq!(entry.name, "billing");
+    assert_eq!(entry.source, NamespaceSource::CommandLine);
+}
+
+#[test]
+fn test_define_replaces_same_name() {
+    let mut registry = NamespaceRegistry::new();
+    registry
+        .define("billing", Uuid::nil(), NamespaceSource::Config)
+        .unwrap();
+    registry
+        .define_from_arg(&format!("BILLING={}", BILLING))
+        .unwrap();
+    assert_eq!(
+        registry.get("billing"),
+        Some(Uuid::parse_str(BILLING).unwrap())
+    );
+    assert_eq!(registry.entries().len(), BUILTIN_NAMESPACES.len() + 1);
+}
+
+// ============================================
+// Validation Tests
+// ============================================
+
+#[test]
+fn test_cannot_redefine_builtin() {
+    let mut registry = NamespaceRegistry::new();
+    let result = registry.define_from_arg(&format!("dns={}", BILLING));
+    assert!(matches!(result, Err(IDError::InvalidNamespace(_))));
+}
+
+#[test]
+fn test_define_from_arg_malformed() {
+    let mut registry = NamespaceRegistry::new();
+    assert!(registry.define_from_arg("billing").is_err());
+    assert!(registry.define_from_arg("billing=not-a-uuid").is_err());
+    assert!(registry
+        .define_from_arg(&format!("acme.com={}", BILLING))
+        .is_err());
+}
+
+// ============================================
+// Config File Tests
+// ============================================
+
+#[test]
+fn test_load_config() {
+    let path = std::env::temp_dir().join(format!("idgen-ns-config-{}.toml", std::process::id()));
+    std::fs::write(
+        &path,
+        format!(
+            "[namespaces]\nbilling = \"{}\"\n\n[other]\nkey = 1\n",
+            BILLING
+        ),
+    )
+    .unwrap();
+
+    let mut registry = NamespaceRegistry::new();
+    let result = registry.load_config(&path);
+    let _ = std::fs::remove_file(&path);
+
+    result.unwrap();
+    assert_eq!(
+        registry.get("billing"),
+        Some(Uuid::parse_str(BILLING).unwrap())
+    );
+    assert_eq!(
+        registry.entries().last().unwrap().source,
+        NamespaceSource::Config
+    );
+}
+
+#[test]
+fn test_load_missing_config_is_ok() {
+    let mut registry = NamespaceRegistry::new();
+    let path = std::env::temp_dir().join("idgen-no-such-config.toml");
+    assert!(registry.load_config(&path).is_ok());
+}
+
+// ============================================
+// Namespace Path Tests
+// ============================================
+
+#[test]
+fn test_namespace_path_derives_each_step() {
+    let registry = NamespaceRegistry::new();
+    let steps = resolve_namespace_path("DNS:acme.com/orders", &registry, Uuid::new_v5).unwrap();
+
+    let acme = Uuid::new_v5(&Uuid::NAMESPACE_DNS, b"acme.com");
+    let orders = Uuid::new_v5(&acme, b"orders");
+    assert_eq!(
+        steps,
+        vec![
+            ("DNS".to_string(), Uuid::NAMESPACE_DNS),
+            ("DNS:acme.com".to_string(), acme),
+            ("DNS:acme.com/orders".to_string(), orders),
+        ]
+    );
+}
+
+#[test]
+fn test_namespace_path_uses_given_hash() {
+    let registry = NamespaceRegistry::new();
+    let steps = resolve_namespace_path("URL:a", &registry, Uuid::new_v3).unwrap();
+    assert_eq!(steps[1].1, Uuid::new_v3(&Uuid::NAMESPACE_URL, b"a"));
+}
+
+#[test]
+fn test_namespace_path_with_custom_base() {
+    let mut registry = NamespaceRegistry::new();
+    registry
+        .define_from_arg(&format!("billing={}", BILLING))
+        .unwrap();
+    let steps = resolve_namespace_path("billing:eu", &registry, Uuid::new_v5).unwrap();
+    assert_eq!(
+        steps[1].1,
+        Uuid::new_v5(&Uuid::parse_str(BILLING).unwrap(), b"eu")
+    );
+}
+
+#[test]
+fn test_namespace_without_path() {
+    let registry = NamespaceRegistry::new();
+    let steps = resolve_namespace_path("DNS", &registry, Uuid::new_v5).unwrap();
+    assert_eq!(steps, vec![("DNS".to_string(), Uuid::NAMESPACE_DNS)]);
+}
+
+#[test]
+fn test_namespace_path_empty_name() {
+    let registry = NamespaceRegistry::new();
+    assert!(resolve_namespace_path("DNS:", &registry, Uuid::new_v5).is_err());
+    assert!(resolve_namespace_path("DNS:a//b", &registry, Uuid::new_v5).is_err());
+}
+
+// ============================================
+// UUID Parsing Tests
+// ============================================
+
+fn parse_error(input: &str) -> String {
+    match parse_namespace_uuid(input) {
+        Err(IDError::InvalidNamespace(msg)) => msg,
+        other => panic!("expected InvalidNamespace for '{}', got {:?}", input, other),
+    }
+}
+
+#[test]
+fn test_parse_namespace_uuid_forms() {
+    let dns = Uuid::NAMESPACE_DNS;
+    for input in [
+        "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+        "6BA7B810-9DAD-11D1-80B4-00C04FD430C8",
+        "6ba7b8109dad11d180b400c04fd430c8",
+        "{6ba7b810-9dad-11d1-80b4-00c04fd430c8}",
+        "urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+        "URN:UUID:6BA7B810-9DAD-11D1-80B4-00C04FD430C8",
+    ] {
+        assert_eq!(parse_namespace_uuid(input).unwrap(), dns, "{}", input);
+    }
+}
+
+#[test]
+fn test_parse_namespace_uuid_reports_character() {
+    assert!(parse_error("6ba7b810-9dad-11d1-80b4-00c04fd430cg")
+        .contains("invalid character 'g' at position 36"));
+    assert!(parse_error("{6ba7b810-9dad-11d1-80b4-00c04fd430zz}")
+        .contains("invalid character 'z' at position 36"));
+    assert!(parse_error("urn:uuid:6ba7b810_9dad-11d1-80b4-00c04fd430c8")
+        .contains("invalid character '_' at position 18"));
+}
+
+#[test]
+fn test_parse_namespace_uuid_reports_group() {
+    assert!(parse_error("6ba7b810-9dad-11d1-80b4-00c04fd430c")
+        .contains("group 5 has 11 hex digits, expected 12"));
+    assert!(parse_error("6ba7b8109-dad-11d1-80b4-00c04fd430c8")
+        .contains("group 1 has 9 hex digits, expected 8"));
+    assert!(parse_error("6ba7b810-9dad11d1-80b4-00c04fd430c8").contains("found 4"));
+    assert!(
+        parse_error("6ba7b8109dad11d180b400c04fd430c").contains("expected 32 hex digits, found 31")
+    );
+    assert!(parse_error("{6ba7b810-9dad-11d1-80b4-00c04fd430c8").contains("missing closing '}'"));
+    assert!(parse_error("").contains("expected 32 hex digits, found 0"));
+}
+
+#[test]
+fn test_parse_namespace_uuid_multibyte_input() {
+    assert!(parse_error("abcdefgh€").contains("invalid character 'g' at position 7"));
+    assert!(parse_error("abcdef€").contains("invalid character '€' at position 7"));
+}
+
+#[test]
+fn test_resolve_namespace_typed_error() {
+    let registry = NamespaceRegistry::new();
+    assert_eq!(
+        resolve_namespace("dns", &registry).unwrap(),
+        Uuid::NAMESPACE_DNS
+    );
+    assert!(matches!(
+        resolve_namespace("billing", &registry),
+        Err(IDError::InvalidNamespace(_))
+    ));
+}
+
+#[test]
+fn test_namespace_path_with_urn_base() {
+    let registry = NamespaceRegistry::new();
+    let steps = resolve_namespace_path(
+        "urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8:acme.com",
+        &registry,
+        Uuid::new_v5,
+    )
+    .unwrap();
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[1].1, Uuid::new_v5(&Uuid::NAMESPACE_DNS, b"acme.com"));
+}
+
+#[test]
+fn test_namespace_path_multibyte_base() {
+    let registry = NamespaceRegistry::new();
+    assert!(matches!(
+        resolve_namespace_path("abcdefgh€:acme.com", &registry, Uuid::new_v5),
+        Err(IDError::InvalidNamespace(_))
+    ));
+}
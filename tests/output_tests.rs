@@ -0,0 +1,92 @@
+use idgen_cli::output::{split_path, AtomicFile, Output};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// A fresh directory for one test
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("idgen-output-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn entries(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+// ============================================
+// Atomic File Tests
+// ============================================
+
+#[test]
+fn test_atomic_file_appears_on_commit() {
+    let dir = temp_dir("commit");
+    let path = dir.join("ids.txt");
+    fs::write(&path, "old\n").unwrap();
+
+    let mut file = AtomicFile::create(&path).unwrap();
+    writeln!(file, "new").unwrap();
+    file.flush().unwrap();
+    // Readers still see the old content until the commit
+    assert_eq!(fs::read_to_string(&path).unwrap(), "old\n");
+
+    file.commit().unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "new\n");
+    assert_eq!(entries(&dir), vec!["ids.txt"]);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_atomic_file_dropped_without_commit() {
+    let dir = temp_dir("drop");
+    let path = dir.join("ids.txt");
+
+    let mut file = AtomicFile::create(&path).unwrap();
+    writeln!(file, "partial").unwrap();
+    drop(file);
+
+    assert!(entries(&dir).is_empty());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_output_append() {
+    let dir = temp_dir("append");
+    let path = dir.join("ids.txt");
+
+    for line in ["a", "b"] {
+        let mut out = Output::file(&path, true).unwrap();
+        assert_eq!(out.has_content().unwrap(), line == "b");
+        writeln!(out, "{}", line).unwrap();
+        out.commit().unwrap();
+    }
+    assert_eq!(fs::read_to_string(&path).unwrap(), "a\nb\n");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// ============================================
+// Split Path Tests
+// ============================================
+
+#[test]
+fn test_split_path() {
+    assert_eq!(
+        split_path(Path::new("ids.txt"), 1),
+        Path::new("ids-0001.txt")
+    );
+    assert_eq!(
+        split_path(Path::new("out/ids.csv"), 12),
+        Path::new("out/ids-0012.csv")
+    );
+    assert_eq!(split_path(Path::new("ids"), 3), Path::new("ids-0003"));
+    assert_eq!(
+        split_path(Path::new("ids.txt"), 12345),
+        Path::new("ids-12345.txt")
+    );
+}
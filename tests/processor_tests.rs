@@ -1,4 +1,4 @@
-use idgen_cli::id::{CuidVersion, IDFormat, UuidVersion};
+use idgen_cli::id::{CuidVersion, IDFormat, UuidV1Fields, UuidVersion};
 
 fn with_args(args: Vec<&str>) -> Vec<String> {
     let mut full_args = vec!["program"];
@@ -24,7 +24,7 @@ fn test_default_format() {
     let mut name: Option<String> = None;
     let mut lastcmd = String::new();
 
-    args.iter().enumerate().for_each(|(_, arg)| {
+    args.iter().for_each(|arg| {
         if arg == "-h" || arg == "--help" {
             help = true;
         } else if arg == "-v" || arg == "--version" {
@@ -36,7 +36,7 @@ fn test_default_format() {
         } else if arg == "-o" || arg == "--objectid" {
             format = IDFormat::OID;
         } else if arg == "-n" || arg == "--nano" {
-            format = IDFormat::NanoID;
+            format = IDFormat::NanoID(None);
         }
 
         if lastcmd == "-c" || lastcmd == "--count" {
@@ -71,7 +71,7 @@ fn test_simple_format() {
     let mut format = IDFormat::Hyphenated(version);
     let mut lastcmd = String::new();
 
-    args.iter().enumerate().for_each(|(_, arg)| {
+    args.iter().for_each(|arg| {
         if arg == "-s" || arg == "--simple" {
             format = IDFormat::Simple(version);
         }
@@ -88,7 +88,7 @@ fn test_uuid_version() {
     let mut format = IDFormat::Hyphenated(version);
     let mut lastcmd = String::new();
 
-    args.iter().enumerate().for_each(|(_, arg)| {
+    args.iter().for_each(|arg| {
         if arg == "-u3" || arg == "--uuid3" {
             version = UuidVersion::V3;
             format = IDFormat::Hyphenated(version);
@@ -105,7 +105,7 @@ fn test_count_option() {
     let mut count = 1;
     let mut lastcmd = String::new();
 
-    args.iter().enumerate().for_each(|(_, arg)| {
+    args.iter().for_each(|arg| {
         if lastcmd == "-c" || lastcmd == "--count" {
             count = arg.parse::<i32>().unwrap_or(1);
         }
@@ -123,16 +123,16 @@ fn test_nanoid_length() {
     let mut len: Option<usize> = None;
     let mut lastcmd = String::new();
 
-    args.iter().enumerate().for_each(|(_, arg)| {
+    args.iter().for_each(|arg| {
         if arg == "-n" || arg == "--nano" {
-            format = IDFormat::NanoID;
+            format = IDFormat::NanoID(None);
         } else if lastcmd == "-n" || lastcmd == "--nano" {
             len = Some(arg.parse::<usize>().unwrap_or(21));
         }
         lastcmd = arg.clone();
     });
 
-    assert!(matches!(format, IDFormat::NanoID));
+    assert!(matches!(format, IDFormat::NanoID(_)));
     assert_eq!(len, Some(10));
 }
 
@@ -142,7 +142,7 @@ fn test_prefix_option() {
     let mut prefix = "";
     let mut lastcmd = String::new();
 
-    args.iter().enumerate().for_each(|(_, arg)| {
+    args.iter().for_each(|arg| {
         if lastcmd == "-p" || lastcmd == "--prefix" {
             prefix = arg;
         }
@@ -158,7 +158,7 @@ fn test_suffix_option() {
     let mut suffix = "";
     let mut lastcmd = String::new();
 
-    args.iter().enumerate().for_each(|(_, arg)| {
+    args.iter().for_each(|arg| {
         if lastcmd == "-f" || lastcmd == "--suffix" {
             suffix = arg;
         }
@@ -183,7 +183,7 @@ fn test_uuid_v3_parameters() {
     let mut name: Option<String> = None;
     let mut lastcmd = String::new();
 
-    args.iter().enumerate().for_each(|(_, arg)| {
+    args.iter().for_each(|arg| {
         if arg == "-u3" || arg == "--uuid3" {
             version = UuidVersion::V3;
             format = IDFormat::Hyphenated(version);
@@ -205,7 +205,7 @@ fn test_banner_flag() {
     let args = with_args(vec!["--banner"]);
     let mut show_banner = false;
 
-    args.iter().enumerate().for_each(|(_, arg)| {
+    args.iter().for_each(|arg| {
         if arg == "-b" || arg == "--banner" {
             show_banner = true;
         }
@@ -220,7 +220,7 @@ fn test_json_flag() {
     let mut json_output = false;
     let mut show_banner = true; // Assume default is true for this test logic check
 
-    args.iter().enumerate().for_each(|(_, arg)| {
+    args.iter().for_each(|arg| {
         if arg == "--json" {
             json_output = true;
             show_banner = false;
@@ -236,7 +236,7 @@ fn test_cuid_v1_flag() {
     let args = with_args(vec!["--cuid1"]);
     let mut format = IDFormat::Hyphenated(UuidVersion::V4);
 
-    args.iter().enumerate().for_each(|(_, arg)| {
+    args.iter().for_each(|arg| {
         if arg == "-c1" || arg == "--cuid1" {
             format = IDFormat::Cuid(CuidVersion::V1);
         }
@@ -254,7 +254,7 @@ fn test_cuid_v2_flag() {
     let args = with_args(vec!["-c2"]);
     let mut format = IDFormat::Hyphenated(UuidVersion::V4);
 
-    args.iter().enumerate().for_each(|(_, arg)| {
+    args.iter().for_each(|arg| {
         if arg == "-c2" || arg == "--cuid2" {
             format = IDFormat::Cuid(CuidVersion::V2);
         }
@@ -272,7 +272,7 @@ fn test_ulid_flag() {
     let args = with_args(vec!["--ulid"]);
     let mut format = IDFormat::Hyphenated(UuidVersion::V4);
 
-    args.iter().enumerate().for_each(|(_, arg)| {
+    args.iter().for_each(|arg| {
         if arg == "-l" || arg == "--ulid" {
             format = IDFormat::Ulid;
         }
@@ -336,11 +336,11 @@ fn test_short_nano_flag() {
 
     args.iter().for_each(|arg| {
         if arg == "-n" || arg == "--nano" {
-            format = IDFormat::NanoID;
+            format = IDFormat::NanoID(None);
         }
     });
 
-    assert!(matches!(format, IDFormat::NanoID));
+    assert!(matches!(format, IDFormat::NanoID(_)));
 }
 
 #[test]
@@ -643,18 +643,18 @@ fn test_uuid_v1_flag() {
 
     args.iter().for_each(|arg| {
         if arg == "-u1" || arg == "--uuid1" {
-            version = UuidVersion::V1;
+            version = UuidVersion::V1(UuidV1Fields::default());
             format = IDFormat::Hyphenated(version);
         }
     });
 
-    assert!(matches!(format, IDFormat::Hyphenated(UuidVersion::V1)));
+    assert!(matches!(format, IDFormat::Hyphenated(UuidVersion::V1(_))));
 }
 
 #[test]
 fn test_uuid_v4_flag() {
     let args = with_args(vec!["--uuid4"]);
-    let mut version = UuidVersion::V1; // Start with V1 to verify change
+    let mut version = UuidVersion::V1(UuidV1Fields::default()); // Start with V1 to verify change
     let mut format = IDFormat::Hyphenated(version);
 
     args.iter().for_each(|arg| {
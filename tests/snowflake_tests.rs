@@ -0,0 +1,191 @@
+use idgen_cli::id::{new_id, IDError, IDFormat};
+use idgen_cli::snowflake::{next_snowflake, snowflake_at, SnowflakeConfig, SnowflakeParts};
+
+// ============================================
+// Layout Tests
+// ============================================
+
+#[test]
+fn test_preset_timestamp_bits() {
+    assert_eq!(SnowflakeConfig::twitter().timestamp_bits(), 41);
+    assert_eq!(SnowflakeConfig::discord().timestamp_bits(), 42);
+    assert_eq!(SnowflakeConfig::instagram().timestamp_bits(), 41);
+}
+
+#[test]
+fn test_decode_discord_example() {
+    // Example from the Discord API reference
+    let parts = SnowflakeConfig::discord().decode(175928847299117063);
+    assert_eq!(
+        parts,
+        SnowflakeParts {
+            timestamp: 1462015105796,
+            datacenter_id: 1,
+            worker_id: 0,
+            sequence: 7,
+        }
+    );
+}
+
+#[test]
+fn test_encode_decode_round_trip() {
+    let config = SnowflakeConfig {
+        datacenter_id: 17,
+        worker_id: 9,
+        ..SnowflakeConfig::twitter()
+    };
+    let id = config.encode(1_700_000_000_123, 42).unwrap();
+    let parts = config.decode(id);
+    assert_eq!(parts.timestamp, 1_700_000_000_123);
+    assert_eq!(parts.datacenter_id, 17);
+    assert_eq!(parts.worker_id, 9);
+    assert_eq!(parts.sequence, 42);
+}
+
+#[test]
+fn test_instagram_shard_in_worker_id() {
+    let config = SnowflakeConfig {
+        worker_id: 5000,
+        ..SnowflakeConfig::instagram()
+    };
+    let id = config.encode(1_400_000_000_000, 1).unwrap();
+    assert_eq!(config.decode(id).worker_id, 5000);
+}
+
+#[test]
+fn test_twitter_keeps_sign_bit_clear() {
+    let config = SnowflakeConfig::twitter();
+    let last = config.epoch + (1 << 41) - 1;
+    let id = config.encode(last, 0).unwrap();
+    assert_eq!(id >> 63, 0);
+    assert_eq!(config.decode(id).timestamp, last);
+    let result = config.encode(last + 1, 0);
+    assert!(matches!(result, Err(IDError::InvalidSnowflake(_))));
+}
+
+#[test]
+fn test_encode_before_epoch() {
+    let result = SnowflakeConfig::discord().encode(0, 0);
+    assert!(matches!(result, Err(IDError::InvalidSnowflake(_))));
+}
+
+// ============================================
+// Validation Tests
+// ============================================
+
+#[test]
+fn test_worker_id_too_large() {
+    let config = SnowflakeConfig {
+        worker_id: 32,
+        ..SnowflakeConfig::twitter()
+    };
+    assert!(matches!(
+        config.validate(),
+        Err(IDError::InvalidSnowflake(_))
+    ));
+}
+
+#[test]
+fn test_datacenter_id_too_large() {
+    let config = SnowflakeConfig {
+        datacenter_id: 1,
+        ..SnowflakeConfig::instagram()
+    };
+    assert!(matches!(
+        config.validate(),
+        Err(IDError::InvalidSnowflake(_))
+    ));
+}
+
+#[test]
+fn test_too_many_field_bits() {
+    let config = SnowflakeConfig {
+        sequence_bits: 30,
+        ..SnowflakeConfig::twitter()
+    };
+    assert!(matches!(
+        config.validate(),
+        Err(IDError::InvalidSnowflake(_))
+    ));
+}
+
+// ============================================
+// Generation Tests
+// ============================================
+
+#[test]
+fn test_snowflake_strictly_increasing() {
+    let config = SnowflakeConfig::twitter();
+    let mut last = 0;
+    for _ in 0..10_000 {
+        let id = next_snowflake(&config).unwrap();
+        assert!(id > last);
+        last = id;
+    }
+}
+
+#[test]
+fn test_snowflake_carries_worker_id() {
+    let config = SnowflakeConfig {
+        datacenter_id: 3,
+        worker_id: 7,
+        ..SnowflakeConfig::twitter()
+    };
+    let id = next_snowflake(&config).unwrap();
+    let parts = config.decode(id);
+    assert_eq!(parts.datacenter_id, 3);
+    assert_eq!(parts.worker_id, 7);
+}
+
+#[test]
+fn test_new_id_snowflake_is_numeric() {
+    let id = new_id(
+        &IDFormat::Snowflake(SnowflakeConfig::discord()),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(id.parse::<u64>().is_ok());
+}
+
+#[test]
+fn test_snowflake_at_same_millisecond() {
+    let config = SnowflakeConfig::twitter();
+    let first = config.decode(snowflake_at(&config, 1_600_000_000_000).unwrap());
+    let second = config.decode(snowflake_at(&config, 1_600_000_000_000).unwrap());
+    assert_eq!(first.timestamp, 1_600_000_000_000);
+    assert_eq!(second.timestamp, 1_600_000_000_000);
+    assert_eq!(second.sequence, first.sequence + 1);
+}
+
+#[test]
+fn test_snowflake_at_sequence_exhausted() {
+    let config = SnowflakeConfig {
+        sequence_bits: 1,
+        ..SnowflakeConfig::twitter()
+    };
+    assert!(snowflake_at(&config, 1_600_000_000_001).is_ok());
+    assert!(snowflake_at(&config, 1_600_000_000_001).is_ok());
+    let result = snowflake_at(&config, 1_600_000_000_001);
+    assert!(matches!(result, Err(IDError::InvalidSnowflake(_))));
+}
+
+#[test]
+fn test_snowflake_at_new_timestamp_restarts_sequence() {
+    let config = SnowflakeConfig::twitter();
+    snowflake_at(&config, 1_600_000_000_002).unwrap();
+    snowflake_at(&config, 1_600_000_000_002).unwrap();
+    let id = config.decode(snowflake_at(&config, 1_600_000_000_003).unwrap());
+    assert_eq!(id.sequence, 0);
+}
+
+#[test]
+fn test_snowflake_at_returning_timestamp_continues_sequence() {
+    let config = SnowflakeConfig::twitter();
+    let first = config.decode(snowflake_at(&config, 1_600_000_000_010).unwrap());
+    snowflake_at(&config, 1_600_000_000_011).unwrap();
+    let again = config.decode(snowflake_at(&config, 1_600_000_000_010).unwrap());
+    assert_eq!(again.timestamp, first.timestamp);
+    assert_eq!(again.sequence, first.sequence + 1);
+}
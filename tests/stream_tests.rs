@@ -0,0 +1,51 @@
+use idgen_cli::stream::Stream;
+use std::time::{Duration, Instant};
+
+// ============================================
+// Pacing Tests
+// ============================================
+
+#[test]
+fn test_stream_unlimited_rate() {
+    let stream = Stream::new(None, None);
+    let started = Instant::now();
+    for i in 0..10_000 {
+        assert!(stream.next(i));
+    }
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn test_stream_rate_spaces_ids() {
+    let stream = Stream::new(None, Some(100.0));
+    let started = Instant::now();
+    for i in 0..=10 {
+        assert!(stream.next(i));
+    }
+    // The 11th ID is due 100ms after the first
+    assert!(started.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+fn test_stream_duration_ends_stream() {
+    let stream = Stream::new(Some(Duration::from_millis(50)), Some(10.0));
+    assert!(stream.next(0));
+    // Due after 100ms, which is past the 50ms limit
+    let started = Instant::now();
+    assert!(!stream.next(1));
+    assert!(started.elapsed() < Duration::from_millis(100));
+}
+
+#[test]
+fn test_stream_huge_duration_has_no_deadline() {
+    let stream = Stream::new(Some(Duration::from_secs(u64::MAX)), None);
+    assert!(stream.next(0));
+}
+
+#[test]
+fn test_stream_tiny_rate_waits_for_deadline() {
+    let stream = Stream::new(Some(Duration::from_millis(50)), Some(1e-300));
+    assert!(stream.next(0));
+    // Never due, so the stream ends at the deadline instead of panicking
+    assert!(!stream.next(1));
+}
@@ -0,0 +1,111 @@
+use idgen_cli::id::{IDError, IDFormat, UuidVersion};
+use idgen_cli::template::{IdPlaceholder, Segment, Template};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Resolves `ulid`, `nanoid` (with an optional length) and `uuid5` for DNS/example.com
+fn resolve(name: &str, options: Option<&str>) -> Result<IdPlaceholder, IDError> {
+    let (format, namespace, id_name) = match name {
+        "ulid" => (IDFormat::Ulid, None, None),
+        "nanoid" => (IDFormat::NanoID(None), None, None),
+        "uuid5" => (
+            IDFormat::Hyphenated(UuidVersion::V5),
+            Some("6ba7b810-9dad-11d1-80b4-00c04fd430c8".to_string()),
+            Some("example.com".to_string()),
+        ),
+        _ => return Err(IDError::InvalidTemplate(format!("unknown {}", name))),
+    };
+    Ok(IdPlaceholder {
+        format,
+        len: options.map(|len| len.parse().unwrap()),
+        namespace,
+        name: id_name,
+    })
+}
+
+fn at() -> SystemTime {
+    // 2024-01-31T12:34:56Z
+    UNIX_EPOCH + Duration::from_secs(1_706_704_496)
+}
+
+// ============================================
+// Parsing Tests
+// ============================================
+
+#[test]
+fn test_template_segments() {
+    let template = Template::parse("ord-{ulid}-{nanoid:6}", resolve).unwrap();
+    let segments = template.segments();
+    assert_eq!(segments.len(), 4);
+    assert!(matches!(&segments[0], Segment::Literal(text) if text == "ord-"));
+    assert!(matches!(&segments[1], Segment::Id(p) if matches!(p.format, IDFormat::Ulid)));
+    assert!(matches!(&segments[3], Segment::Id(p) if p.len == Some(6)));
+}
+
+#[test]
+fn test_template_escaped_braces() {
+    let template = Template::parse("{{literal}}-{seq}", resolve).unwrap();
+    assert_eq!(template.render(7, None).unwrap(), "{literal}-7");
+}
+
+#[test]
+fn test_template_errors() {
+    for bad in ["{ulid", "a}b", "{}", "{seq:x}", "{date:%Q}", "{unknown}"] {
+        assert!(
+            matches!(
+                Template::parse(bad, resolve),
+                Err(IDError::InvalidTemplate(_))
+            ),
+            "{}",
+            bad
+        );
+    }
+}
+
+#[test]
+fn test_template_uses_time() {
+    assert!(Template::parse("{ulid}", resolve).unwrap().uses_time());
+    assert!(Template::parse("{date}", resolve).unwrap().uses_time());
+    assert!(!Template::parse("{nanoid}-{seq}", resolve)
+        .unwrap()
+        .uses_time());
+}
+
+// ============================================
+// Rendering Tests
+// ============================================
+
+#[test]
+fn test_template_seq_and_date() {
+    let template = Template::parse("{date:%Y%m%d}-{seq:06}-{seq:3}", resolve).unwrap();
+    assert_eq!(
+        template.render(42, Some(at())).unwrap(),
+        "20240131-000042- 42"
+    );
+
+    let template = Template::parse("{date}", resolve).unwrap();
+    assert_eq!(
+        template.render(1, Some(at())).unwrap(),
+        "2024-01-31T12:34:56Z"
+    );
+}
+
+#[test]
+fn test_template_ids() {
+    let template = Template::parse("ord-{ulid}-{nanoid:6}", resolve).unwrap();
+    let id = template.render(1, Some(at())).unwrap();
+    let parts: Vec<&str> = id.splitn(3, '-').collect();
+    assert_eq!(parts[0], "ord");
+    assert_eq!(parts[1].len(), 26);
+    // The ULID carries the given time; the NanoID ignores it
+    assert!(parts[1].starts_with("01HNFR7SC0"));
+    assert_eq!(parts[2].len(), 6);
+}
+
+#[test]
+fn test_template_name_based_id() {
+    let template = Template::parse("{uuid5}", resolve).unwrap();
+    assert_eq!(
+        template.render(1, None).unwrap(),
+        "cfbff0d1-9375-5685-968c-48ce8b15ae17"
+    );
+}
@@ -0,0 +1,95 @@
+use idgen_cli::id::IDError;
+use idgen_cli::typeid::{decode_suffix, encode_suffix, validate_prefix, TypeId};
+use uuid::Uuid;
+
+// ============================================
+// Encoding Tests (vectors from the TypeID spec)
+// ============================================
+
+#[test]
+fn test_encode_nil_and_max() {
+    assert_eq!(encode_suffix(&Uuid::nil()), "00000000000000000000000000");
+    assert_eq!(encode_suffix(&Uuid::max()), "7zzzzzzzzzzzzzzzzzzzzzzzzz");
+}
+
+#[test]
+fn test_spec_vector_round_trip() {
+    let uuid = Uuid::parse_str("01890a5d-ac96-774b-bcce-b302099a8057").unwrap();
+    assert_eq!(encode_suffix(&uuid), "01h455vb4pex5vsknk084sn02q");
+    assert_eq!(decode_suffix("01h455vb4pex5vsknk084sn02q").unwrap(), uuid);
+}
+
+#[test]
+fn test_parse_with_prefix() {
+    let typeid = TypeId::parse("prefix_01h455vb4pex5vsknk084sn02q").unwrap();
+    assert_eq!(typeid.prefix, "prefix");
+    assert_eq!(
+        typeid.uuid.to_string(),
+        "01890a5d-ac96-774b-bcce-b302099a8057"
+    );
+    assert_eq!(typeid.to_string(), "prefix_01h455vb4pex5vsknk084sn02q");
+}
+
+#[test]
+fn test_parse_prefix_with_underscores() {
+    let typeid = TypeId::parse("pre_fix_00000000000000000000000000").unwrap();
+    assert_eq!(typeid.prefix, "pre_fix");
+}
+
+#[test]
+fn test_parse_without_prefix() {
+    let typeid = TypeId::parse("00000000000000000000000000").unwrap();
+    assert_eq!(typeid.prefix, "");
+    assert_eq!(typeid.to_string(), "00000000000000000000000000");
+}
+
+#[test]
+fn test_new_uses_uuid_v7() {
+    let typeid = TypeId::new("user").unwrap();
+    assert_eq!(typeid.uuid.get_version_num(), 7);
+    assert!(typeid.to_string().starts_with("user_"));
+}
+
+// ============================================
+// Validation Tests
+// ============================================
+
+#[test]
+fn test_invalid_prefixes() {
+    for prefix in ["User", "user1", "_user", "user_", "us-er", &"a".repeat(64)] {
+        assert!(
+            matches!(validate_prefix(prefix), Err(IDError::InvalidTypeId(_))),
+            "prefix {:?} should be rejected",
+            prefix
+        );
+    }
+    assert!(validate_prefix("").is_ok());
+    assert!(validate_prefix(&"a".repeat(63)).is_ok());
+}
+
+#[test]
+fn test_suffix_overflow() {
+    assert!(matches!(
+        decode_suffix("8zzzzzzzzzzzzzzzzzzzzzzzzz"),
+        Err(IDError::InvalidTypeId(_))
+    ));
+}
+
+#[test]
+fn test_suffix_invalid_characters() {
+    // Uppercase and the excluded letters i, l, o, u are not part of the alphabet
+    for suffix in ["01H455VB4PEX5VSKNK084SN02Q", "0000000000000000000000000u"] {
+        assert!(matches!(
+            decode_suffix(suffix),
+            Err(IDError::InvalidTypeId(_))
+        ));
+    }
+}
+
+#[test]
+fn test_empty_prefix_with_separator() {
+    assert!(matches!(
+        TypeId::parse("_00000000000000000000000000"),
+        Err(IDError::InvalidTypeId(_))
+    ));
+}
@@ -0,0 +1,64 @@
+use idgen_cli::xid::{Xid, XID_LENGTH};
+
+// ============================================
+// Encoding Tests (vector from rs/xid)
+// ============================================
+
+#[test]
+fn test_xid_reference_value() {
+    let xid = Xid::parse("9m4e2mr0ui3e8a215n4g").unwrap();
+    assert_eq!(xid.timestamp, 1300816219);
+    assert_eq!(xid.machine_id, [0x60, 0xf4, 0x86]);
+    assert_eq!(xid.pid, 0xe428);
+    assert_eq!(xid.counter, 4271561);
+    assert_eq!(
+        xid.to_bytes(),
+        [0x4d, 0x88, 0xe1, 0x5b, 0x60, 0xf4, 0x86, 0xe4, 0x28, 0x41, 0x2d, 0xc9]
+    );
+    assert_eq!(xid.to_string(), "9m4e2mr0ui3e8a215n4g");
+}
+
+#[test]
+fn test_xid_round_trip() {
+    let xid = Xid {
+        timestamp: 1_700_000_000,
+        machine_id: [0xab, 0xcd, 0xef],
+        pid: 4242,
+        counter: 0x00FF_FFFF,
+    };
+    let s = xid.to_string();
+    assert_eq!(s.len(), XID_LENGTH);
+    assert_eq!(Xid::parse(&s), Some(xid));
+}
+
+#[test]
+fn test_xid_counter_increments() {
+    let a = Xid::new();
+    let b = Xid::new();
+    assert_eq!(b.counter, (a.counter + 1) & 0x00FF_FFFF);
+    assert_eq!(a.machine_id, b.machine_id);
+    assert_eq!(a.pid, b.pid);
+}
+
+// ============================================
+// Parsing Error Tests
+// ============================================
+
+#[test]
+fn test_xid_parse_wrong_length() {
+    assert_eq!(Xid::parse("9m4e2mr0ui3e8a215n4"), None);
+    assert_eq!(Xid::parse("9m4e2mr0ui3e8a215n4g0"), None);
+}
+
+#[test]
+fn test_xid_parse_invalid_chars() {
+    // 'w' is outside base32hex and uppercase is not accepted
+    assert_eq!(Xid::parse("9m4e2mr0ui3e8a215n4w"), None);
+    assert_eq!(Xid::parse("9M4E2MR0UI3E8A215N4G"), None);
+}
+
+#[test]
+fn test_xid_parse_non_canonical_last_char() {
+    // The last character only carries one bit, so it must be '0' or 'g'
+    assert_eq!(Xid::parse("9m4e2mr0ui3e8a215n4h"), None);
+}